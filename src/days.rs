@@ -3,27 +3,55 @@ use aoc_lib::Day;
 mod day01;
 mod day02;
 mod day03;
+mod day04;
 mod day05;
 mod day06;
 mod day07;
 mod day08;
 mod day09;
 mod day10;
+mod day11;
+mod day12;
 mod day13;
+mod day14;
+mod day15;
+mod day16;
 mod day17;
 mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+pub use day17::run_debugger as run_day17_debugger;
 
 pub static DAYS: &[Day] = &[
     day01::DAY,
     day02::DAY,
     day03::DAY,
+    day04::DAY,
     day05::DAY,
     day06::DAY,
     day07::DAY,
     day08::DAY,
     day09::DAY,
     day10::DAY,
+    day11::DAY,
+    day12::DAY,
     day13::DAY,
+    day14::DAY,
+    day15::DAY,
+    day16::DAY,
     day17::DAY,
     day18::DAY,
+    day19::DAY,
+    day20::DAY,
+    day21::DAY,
+    day22::DAY,
+    day23::DAY,
+    day24::DAY,
+    day25::DAY,
 ];
@@ -0,0 +1,155 @@
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 4,
+    name: "Ceres Search",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part1(&data)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part2(&data)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+struct Grid {
+    tiles: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Grid {
+    fn get(&self, x: isize, y: isize) -> Option<u8> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+
+        self.tiles
+            .get(y as usize * self.width + x as usize)
+            .copied()
+    }
+}
+
+fn parse(input: &str) -> Result<Grid> {
+    let mut tiles = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+
+    for row in input.trim().lines() {
+        let row = row.trim().as_bytes();
+        if *width.get_or_insert(row.len()) != row.len() {
+            return Err(eyre!("Rows have differing lengths"));
+        }
+
+        tiles.extend_from_slice(row);
+        height += 1;
+    }
+
+    Ok(Grid {
+        tiles,
+        width: width.unwrap_or(0),
+        height,
+    })
+}
+
+const DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+fn part1(grid: &Grid) -> usize {
+    let mut count = 0;
+
+    for y in 0..grid.height as isize {
+        for x in 0..grid.width as isize {
+            if grid.get(x, y) != Some(b'X') {
+                continue;
+            }
+
+            for (dx, dy) in DIRECTIONS {
+                let found = b"MAS"
+                    .iter()
+                    .zip(1..)
+                    .all(|(&c, i)| grid.get(x + dx * i, y + dy * i) == Some(c));
+                count += found as usize;
+            }
+        }
+    }
+
+    count
+}
+
+fn part2(grid: &Grid) -> usize {
+    let mut count = 0;
+
+    for y in 1..grid.height as isize - 1 {
+        for x in 1..grid.width as isize - 1 {
+            if grid.get(x, y) != Some(b'A') {
+                continue;
+            }
+
+            let is_mas =
+                |a, b| matches!((a, b), (Some(b'M'), Some(b'S')) | (Some(b'S'), Some(b'M')));
+            let diag_a = is_mas(grid.get(x - 1, y - 1), grid.get(x + 1, y + 1));
+            let diag_b = is_mas(grid.get(x + 1, y - 1), grid.get(x - 1, y + 1));
+
+            count += (diag_a && diag_b) as usize;
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn part1_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 18;
+        let actual = part1(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 9;
+        let actual = part2(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+}
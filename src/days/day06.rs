@@ -5,7 +5,7 @@ pub const DAY: Day = Day {
     day: 6,
     name: "Guard Gallivant",
     part_1: run_part1,
-    part_2: None,
+    part_2: Some(run_part2),
     other: &[("Parse", run_parse)],
 };
 
@@ -14,6 +14,11 @@ fn run_part1(input: &str, b: Bench) -> BenchResult {
     b.bench(|| Ok::<_, NoError>(part1(&map, point)))
 }
 
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let (map, point) = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part2(&map, point)))
+}
+
 fn run_parse(input: &str, b: Bench) -> BenchResult {
     b.bench(|| {
         let data = parse(input).map_err(UserError)?;
@@ -38,6 +43,10 @@ impl Direction {
             Direction::Right => Direction::Down,
         }
     }
+
+    fn mask(self) -> u8 {
+        1 << self as u8
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -93,6 +102,20 @@ impl Map {
     fn tile(&self, p: Point) -> Option<Tile> {
         self.tiles.get(self.index(p)).copied()
     }
+
+    /// Moves the guard one step, turning right until the way ahead is clear. The new position is
+    /// off the map if the guard walked off an edge.
+    fn walk(&self, pos: Point, mut dir: Direction) -> (Point, Direction) {
+        loop {
+            let next = pos.step(dir);
+            // Off the map, `index` would wrap around into a neighbouring row.
+            if !self.contains(next) || self.tile(next) != Some(Tile::Wall) {
+                return (next, dir);
+            }
+
+            dir = dir.turn();
+        }
+    }
 }
 
 fn parse(input: &str) -> Result<(Map, Point)> {
@@ -133,17 +156,65 @@ fn part1(map: &Map, start: Point) -> usize {
 
     while map.contains(cur_pos) {
         visited[map.index(cur_pos)] = true;
-        let mut next = cur_pos.step(cur_dir);
-        while let Some(Tile::Wall) = map.tile(next) {
-            cur_dir = cur_dir.turn();
-            next = cur_pos.step(cur_dir);
-        }
-        cur_pos = next;
+        (cur_pos, cur_dir) = map.walk(cur_pos, cur_dir);
     }
 
     visited.into_iter().filter(|l| *l).count()
 }
 
+fn is_loop(map: &Map, start: Point, seen: &mut [u8]) -> bool {
+    seen.fill(0);
+    let mut cur_pos = start;
+    let mut cur_dir = Direction::Up;
+
+    while map.contains(cur_pos) {
+        let idx = map.index(cur_pos);
+        if seen[idx] & cur_dir.mask() != 0 {
+            return true;
+        }
+        seen[idx] |= cur_dir.mask();
+
+        (cur_pos, cur_dir) = map.walk(cur_pos, cur_dir);
+    }
+
+    false
+}
+
+fn part2(map: &Map, start: Point) -> usize {
+    let mut map = Map {
+        tiles: map.tiles.clone(),
+        width: map.width,
+        height: map.height,
+    };
+
+    // Only cells on the original route can change where the guard goes.
+    let mut route = Vec::new();
+    let mut visited = vec![false; map.tiles.len()];
+    let mut cur_pos = start;
+    let mut cur_dir = Direction::Up;
+
+    while map.contains(cur_pos) {
+        let idx = map.index(cur_pos);
+        if !visited[idx] && cur_pos != start {
+            route.push(idx);
+        }
+        visited[idx] = true;
+
+        (cur_pos, cur_dir) = map.walk(cur_pos, cur_dir);
+    }
+
+    let mut seen = vec![0; map.tiles.len()];
+    let mut loops = 0;
+
+    for idx in route {
+        map.tiles[idx] = Tile::Wall;
+        loops += is_loop(&map, start, &mut seen) as usize;
+        map.tiles[idx] = Tile::Empty;
+    }
+
+    loops
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,4 +234,25 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn edge_test() {
+        // Walking off the right edge must not wrap around onto the wall on the next row.
+        let (map, point) = parse("..#.\n..^.\n#...\n....").unwrap();
+        assert_eq!(2, part1(&map, point));
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let (map, point) = parse(&data).unwrap();
+        let expected = 6;
+        let actual = part2(&map, point);
+
+        assert_eq!(expected, actual);
+    }
 }
@@ -7,7 +7,7 @@ pub const DAY: Day = Day {
     day: 10,
     name: "Hool It",
     part_1: run_part1,
-    part_2: None,
+    part_2: Some(run_part2),
     other: &[("Parse", run_parse)],
 };
 
@@ -16,6 +16,11 @@ fn run_part1(input: &str, b: Bench) -> BenchResult {
     b.bench(|| Ok::<_, NoError>(part1(&data)))
 }
 
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part2(&data)))
+}
+
 fn run_parse(input: &str, b: Bench) -> BenchResult {
     b.bench(|| {
         let data = parse(input).map_err(UserError)?;
@@ -90,7 +95,7 @@ fn parse(input: &str) -> Result<Map> {
 
     for (y, row) in input.trim().lines().enumerate() {
         for (x, mut tile) in row.trim().bytes().enumerate() {
-            tile -= b'0';
+            tile = tile.wrapping_sub(b'0');
             tiles.push(tile);
 
             if tile == 0 {
@@ -153,6 +158,44 @@ fn part1(map: &Map) -> usize {
     total
 }
 
+fn part2(map: &Map) -> usize {
+    let mut by_height: [Vec<Point>; 10] = Default::default();
+    for y in 0..map.height {
+        for x in 0..map.width {
+            let p = Point {
+                x: x as u8,
+                y: y as u8,
+            };
+            let Some(tile) = map.tile(p) else {
+                continue;
+            };
+            if let Some(bucket) = by_height.get_mut(tile as usize) {
+                bucket.push(p);
+            }
+        }
+    }
+
+    // ratings[i] is the number of distinct trails from tile i up to any 9.
+    let mut ratings = vec![0; map.tiles.len()];
+    for p in &by_height[9] {
+        ratings[map.index(*p)] = 1;
+    }
+
+    for height in (0..9).rev() {
+        for &p in &by_height[height] {
+            let mut rating = 0;
+            for nb in p.neighbours() {
+                if map.contains(nb) && map.tile(nb) == Some(height as u8 + 1) {
+                    rating += ratings[map.index(nb)];
+                }
+            }
+            ratings[map.index(p)] = rating;
+        }
+    }
+
+    map.starts.iter().map(|s| ratings[map.index(*s)]).sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,4 +220,24 @@ mod tests {
             assert_eq!(expected, actual, "{id}");
         }
     }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part2, 1)
+            .open()
+            .unwrap();
+
+        for (id, case) in data.split("---").enumerate() {
+            let Some((test, result)) = case.split_once("\n\n") else {
+                panic!("bad test input");
+            };
+
+            let parsed = parse(test).unwrap();
+            let expected: usize = result.trim().parse().unwrap();
+            let actual = part2(&parsed);
+
+            assert_eq!(expected, actual, "{id}");
+        }
+    }
 }
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result};
+
+pub const DAY: Day = Day {
+    day: 11,
+    name: "Plutonian Pebbles",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part(&data, 25)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part(&data, 75)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+fn parse(input: &str) -> Result<Vec<u64>> {
+    input
+        .split_ascii_whitespace()
+        .map(|s| Ok(s.parse()?))
+        .collect()
+}
+
+fn split_digits(stone: u64) -> Option<(u64, u64)> {
+    let digits = stone.ilog10() + 1;
+    if !digits.is_multiple_of(2) {
+        return None;
+    }
+
+    let divisor = 10u64.pow(digits / 2);
+    Some((stone / divisor, stone % divisor))
+}
+
+fn part(stones: &[u64], blinks: usize) -> u64 {
+    // The order of the stones never matters, so we only track how many of each value there are.
+    let mut counts: HashMap<u64, u64> = HashMap::new();
+    let mut next_counts = HashMap::new();
+
+    for &stone in stones {
+        *counts.entry(stone).or_default() += 1;
+    }
+
+    for _ in 0..blinks {
+        next_counts.clear();
+
+        for (&stone, &count) in &counts {
+            if stone == 0 {
+                *next_counts.entry(1).or_default() += count;
+            } else if let Some((left, right)) = split_digits(stone) {
+                *next_counts.entry(left).or_default() += count;
+                *next_counts.entry(right).or_default() += count;
+            } else {
+                *next_counts.entry(stone * 2024).or_default() += count;
+            }
+        }
+
+        std::mem::swap(&mut counts, &mut next_counts);
+    }
+
+    counts.values().sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn blink_test() {
+        let parsed = parse("0 1 10 99 999").unwrap();
+        let expected = 7;
+        let actual = part(&parsed, 1);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn generations_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+
+        for (blinks, expected) in [(0, 2), (1, 3), (2, 4), (3, 5), (4, 9), (5, 13), (6, 22)] {
+            let actual = part(&parsed, blinks);
+            assert_eq!(expected, actual, "{blinks}");
+        }
+    }
+
+    #[test]
+    fn part1_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 55312;
+        let actual = part(&parsed, 25);
+
+        assert_eq!(expected, actual);
+    }
+}
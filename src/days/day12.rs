@@ -0,0 +1,185 @@
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 12,
+    name: "Garden Groups",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part::<true>(&data)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part::<false>(&data)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+struct Map {
+    tiles: Vec<u8>,
+    width: usize,
+    height: usize,
+}
+
+impl Map {
+    fn index(&self, x: isize, y: isize) -> Option<usize> {
+        if x < 0 || y < 0 || x as usize >= self.width || y as usize >= self.height {
+            return None;
+        }
+
+        Some(y as usize * self.width + x as usize)
+    }
+
+    fn tile(&self, x: isize, y: isize) -> Option<u8> {
+        self.index(x, y).map(|i| self.tiles[i])
+    }
+}
+
+fn parse(input: &str) -> Result<Map> {
+    let mut tiles = Vec::new();
+    let mut width = None;
+    let mut height = 0;
+
+    for row in input.trim().lines() {
+        let row = row.trim().as_bytes();
+        if *width.get_or_insert(row.len()) != row.len() {
+            return Err(eyre!("Rows have differing lengths"));
+        }
+
+        tiles.extend_from_slice(row);
+        height += 1;
+    }
+
+    Ok(Map {
+        tiles,
+        width: width.unwrap_or(0),
+        height,
+    })
+}
+
+const NEIGHBOURS: [(isize, isize); 4] = [(0, -1), (0, 1), (-1, 0), (1, 0)];
+const DIAGONALS: [(isize, isize); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+
+fn part<const P1: bool>(map: &Map) -> usize {
+    let mut visited = vec![false; map.tiles.len()];
+    let mut queue = Vec::new();
+    let mut total = 0;
+
+    for y in 0..map.height as isize {
+        for x in 0..map.width as isize {
+            let Some(start_idx) = map.index(x, y) else {
+                continue;
+            };
+            if visited[start_idx] {
+                continue;
+            }
+
+            let plant = map.tiles[start_idx];
+            let same = |x, y| map.tile(x, y) == Some(plant);
+
+            let mut area = 0;
+            let mut fences = 0;
+
+            visited[start_idx] = true;
+            queue.push((x, y));
+
+            while let Some((cx, cy)) = queue.pop() {
+                area += 1;
+
+                if P1 {
+                    for (dx, dy) in NEIGHBOURS {
+                        fences += !same(cx + dx, cy + dy) as usize;
+                    }
+                } else {
+                    // A region has as many sides as it has corners. Each diagonal around a tile is
+                    // either an outside corner (both orthogonal neighbours differ), or an inside
+                    // corner (both match, but the diagonal does not).
+                    for (dx, dy) in DIAGONALS {
+                        let horiz = same(cx + dx, cy);
+                        let vert = same(cx, cy + dy);
+                        let diag = same(cx + dx, cy + dy);
+
+                        fences += (!horiz && !vert) as usize;
+                        fences += (horiz && vert && !diag) as usize;
+                    }
+                }
+
+                for (dx, dy) in NEIGHBOURS {
+                    let (nx, ny) = (cx + dx, cy + dy);
+                    if !same(nx, ny) {
+                        continue;
+                    }
+
+                    let Some(idx) = map.index(nx, ny) else {
+                        continue;
+                    };
+                    if !visited[idx] {
+                        visited[idx] = true;
+                        queue.push((nx, ny));
+                    }
+                }
+            }
+
+            total += area * fences;
+        }
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn part1_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        for (id, case) in data.split("---").enumerate() {
+            let Some((test, result)) = case.split_once("\n\n") else {
+                panic!("bad test input");
+            };
+
+            let parsed = parse(test).unwrap();
+            let expected: usize = result.trim().parse().unwrap();
+            let actual = part::<true>(&parsed);
+
+            assert_eq!(expected, actual, "{id}");
+        }
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part2, 1)
+            .open()
+            .unwrap();
+
+        for (id, case) in data.split("---").enumerate() {
+            let Some((test, result)) = case.split_once("\n\n") else {
+                panic!("bad test input");
+            };
+
+            let parsed = parse(test).unwrap();
+            let expected: usize = result.trim().parse().unwrap();
+            let actual = part::<false>(&parsed);
+
+            assert_eq!(expected, actual, "{id}");
+        }
+    }
+}
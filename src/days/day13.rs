@@ -10,7 +10,7 @@ pub const DAY: Day = Day {
     day: 13,
     name: "Claw Contraption",
     part_1: run_part1,
-    part_2: None,
+    part_2: Some(run_part2),
     other: &[("Parse", run_parse)],
 };
 
@@ -19,6 +19,11 @@ fn run_part1(input: &str, b: Bench) -> BenchResult {
     b.bench(|| Ok::<_, NoError>(part1(&data)))
 }
 
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part2(&data)))
+}
+
 fn run_parse(input: &str, b: Bench) -> BenchResult {
     b.bench(|| {
         let data = parse(input).map_err(UserError)?;
@@ -28,8 +33,8 @@ fn run_parse(input: &str, b: Bench) -> BenchResult {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct Point {
-    x: u16,
-    y: u16,
+    x: u64,
+    y: u64,
 }
 
 impl Add for Point {
@@ -230,6 +235,40 @@ fn part1(machines: &[Machine]) -> u32 {
     sum
 }
 
+const PRIZE_OFFSET: u64 = 10000000000000;
+
+fn solve(machine: &Machine, offset: u64) -> Option<u64> {
+    let (ax, ay) = (machine.btn_a.x as i64, machine.btn_a.y as i64);
+    let (bx, by) = (machine.btn_b.x as i64, machine.btn_b.y as i64);
+    let px = (machine.prize.x + offset) as i64;
+    let py = (machine.prize.y + offset) as i64;
+
+    // Cramer's rule. None of the inputs have parallel buttons, so a zero
+    // determinant is treated as unwinnable.
+    let det = ax * by - ay * bx;
+    if det == 0 {
+        return None;
+    }
+
+    let a_num = px * by - py * bx;
+    let b_num = ax * py - ay * px;
+    if a_num % det != 0 || b_num % det != 0 {
+        return None;
+    }
+
+    let a_count = a_num / det;
+    let b_count = b_num / det;
+    if a_count < 0 || b_count < 0 {
+        return None;
+    }
+
+    Some(a_count as u64 * 3 + b_count as u64)
+}
+
+fn part2(machines: &[Machine]) -> u64 {
+    machines.iter().filter_map(|m| solve(m, PRIZE_OFFSET)).sum()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -248,4 +287,32 @@ mod tests {
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn solve_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 480;
+        let actual: u64 = parsed.iter().filter_map(|m| solve(m, 0)).sum();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 875318608908;
+        let actual = part2(&parsed);
+
+        assert_eq!(expected, actual);
+    }
 }
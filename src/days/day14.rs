@@ -0,0 +1,168 @@
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 14,
+    name: "Restroom Redoubt",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part1(&data, 101, 103)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| part2(&data, 101, 103))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Robot {
+    pos: Point,
+    vel: Point,
+}
+
+impl Robot {
+    fn position_at(self, seconds: i32, width: i32, height: i32) -> Point {
+        Point {
+            x: (self.pos.x + self.vel.x * seconds).rem_euclid(width),
+            y: (self.pos.y + self.vel.y * seconds).rem_euclid(height),
+        }
+    }
+}
+
+fn parse_point(s: &str, prefix: &str) -> Result<Point> {
+    let (x, y) = s
+        .strip_prefix(prefix)
+        .and_then(|s| s.split_once(','))
+        .ok_or_else(|| eyre!("Invalid point: {s}"))?;
+
+    Ok(Point {
+        x: x.parse()?,
+        y: y.parse()?,
+    })
+}
+
+fn parse(input: &str) -> Result<Vec<Robot>> {
+    let mut robots = Vec::new();
+
+    for line in input.trim().lines() {
+        let Some((pos, vel)) = line.trim().split_once(' ') else {
+            return Err(eyre!("Invalid robot: {line}"));
+        };
+
+        robots.push(Robot {
+            pos: parse_point(pos, "p=")?,
+            vel: parse_point(vel, "v=")?,
+        });
+    }
+
+    Ok(robots)
+}
+
+fn part1(robots: &[Robot], width: i32, height: i32) -> u32 {
+    let mid_x = width / 2;
+    let mid_y = height / 2;
+    let mut quadrants = [0; 4];
+
+    for robot in robots {
+        let pos = robot.position_at(100, width, height);
+        if pos.x == mid_x || pos.y == mid_y {
+            continue;
+        }
+
+        let idx = (pos.x > mid_x) as usize + (pos.y > mid_y) as usize * 2;
+        quadrants[idx] += 1;
+    }
+
+    quadrants.iter().product()
+}
+
+/// Returns the offset within one period that minimises the variance of the given axis.
+fn min_variance_offset(robots: &[Robot], period: i32, axis: impl Fn(Point) -> i32) -> i32 {
+    let n = robots.len() as i64;
+
+    (0..period)
+        .min_by_key(|&t| {
+            let (sum, sum_sq) = robots.iter().fold((0i64, 0i64), |(sum, sum_sq), r| {
+                let p = axis(Point {
+                    x: r.pos.x + r.vel.x * t,
+                    y: r.pos.y + r.vel.y * t,
+                })
+                .rem_euclid(period) as i64;
+                (sum + p, sum_sq + p * p)
+            });
+
+            // Variance scaled by n^2, which doesn't change the ordering.
+            n * sum_sq - sum * sum
+        })
+        .unwrap_or(0)
+}
+
+fn part2(robots: &[Robot], width: i32, height: i32) -> Result<i32> {
+    // The X and Y coordinates repeat independently every `width` and `height` seconds, and the
+    // picture is where the robots are most clustered on both axes at once. Find the tightest
+    // second for each axis, then combine them with the Chinese remainder theorem.
+    let best_x = min_variance_offset(robots, width, |p| p.x);
+    let best_y = min_variance_offset(robots, height, |p| p.y);
+
+    (0..width)
+        .map(|i| best_y + i * height)
+        .find(|t| t % width == best_x)
+        .ok_or_else(|| eyre!("Grid width and height are not coprime"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn part1_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 12;
+        let actual = part1(&parsed, 11, 7);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test() {
+        // Robots that all meet at the same tile after 30 seconds.
+        let target = Point { x: 5, y: 3 };
+        let robots: Vec<_> = [(1, 2), (-3, 1), (4, -2), (2, 3), (-1, -3), (3, 5)]
+            .into_iter()
+            .map(|(x, y)| {
+                let vel = Point { x, y };
+                let pos = Robot { pos: target, vel }.position_at(-30, 11, 7);
+                Robot { pos, vel }
+            })
+            .collect();
+
+        let expected = 30;
+        let actual = part2(&robots, 11, 7).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,272 @@
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 15,
+    name: "Warehouse Woes",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part1(&data)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part2(&data)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tile {
+    Empty,
+    Wall,
+    Box,
+    BoxLeft,
+    BoxRight,
+}
+
+#[derive(Debug, Clone)]
+struct Map {
+    tiles: Vec<Tile>,
+    width: usize,
+    robot: usize,
+}
+
+impl Map {
+    fn step(&self, idx: usize, dir: Direction) -> usize {
+        // The map is surrounded by walls, so we never step outside of it.
+        match dir {
+            Direction::Up => idx - self.width,
+            Direction::Down => idx + self.width,
+            Direction::Left => idx - 1,
+            Direction::Right => idx + 1,
+        }
+    }
+
+    fn widen(&self) -> Self {
+        let tiles = self
+            .tiles
+            .iter()
+            .flat_map(|t| match t {
+                Tile::Box => [Tile::BoxLeft, Tile::BoxRight],
+                t => [*t, *t],
+            })
+            .collect();
+
+        Self {
+            tiles,
+            width: self.width * 2,
+            robot: (self.robot / self.width) * self.width * 2 + (self.robot % self.width) * 2,
+        }
+    }
+
+    fn push(&mut self, dir: Direction, to_move: &mut Vec<usize>) {
+        let is_vertical = matches!(dir, Direction::Up | Direction::Down);
+
+        to_move.clear();
+        to_move.push(self.robot);
+
+        // Gather everything that would be shoved, in the order we reach it. Wide boxes pushed
+        // vertically also drag their other half along, so this can fan out into a tree.
+        let mut i = 0;
+        while let Some(&cur) = to_move.get(i) {
+            i += 1;
+            let next = self.step(cur, dir);
+            if to_move.contains(&next) {
+                continue;
+            }
+
+            match self.tiles[next] {
+                Tile::Empty => {}
+                Tile::Wall => return,
+                Tile::Box => to_move.push(next),
+                Tile::BoxLeft => {
+                    to_move.push(next);
+                    if is_vertical {
+                        to_move.push(next + 1);
+                    }
+                }
+                Tile::BoxRight => {
+                    to_move.push(next);
+                    if is_vertical {
+                        to_move.push(next - 1);
+                    }
+                }
+            }
+        }
+
+        // Move the furthest tiles first so nothing gets overwritten.
+        for &cur in to_move.iter().rev() {
+            let next = self.step(cur, dir);
+            self.tiles[next] = self.tiles[cur];
+            self.tiles[cur] = Tile::Empty;
+        }
+
+        self.robot = self.step(self.robot, dir);
+    }
+
+    fn gps_sum(&self) -> usize {
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| matches!(t, Tile::Box | Tile::BoxLeft))
+            .map(|(i, _)| (i / self.width) * 100 + i % self.width)
+            .sum()
+    }
+}
+
+fn parse(input: &str) -> Result<(Map, Vec<Direction>)> {
+    let (map_str, moves_str) = input
+        .trim()
+        .split_once("\n\n")
+        .ok_or_else(|| eyre!("Invalid input"))?;
+
+    let mut tiles = Vec::new();
+    let mut width = None;
+    let mut robot = None;
+
+    for row in map_str.lines() {
+        let row = row.trim().as_bytes();
+        if *width.get_or_insert(row.len()) != row.len() {
+            return Err(eyre!("Rows have differing lengths"));
+        }
+
+        for &tile in row {
+            let tile = match tile {
+                b'#' => Tile::Wall,
+                b'O' => Tile::Box,
+                b'.' => Tile::Empty,
+                b'@' => {
+                    robot = Some(tiles.len());
+                    Tile::Empty
+                }
+                _ => return Err(eyre!("Invalid tile: {}", tile as char)),
+            };
+
+            tiles.push(tile);
+        }
+    }
+
+    let moves = moves_str
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .map(|b| match b {
+            b'^' => Ok(Direction::Up),
+            b'v' => Ok(Direction::Down),
+            b'<' => Ok(Direction::Left),
+            b'>' => Ok(Direction::Right),
+            _ => Err(eyre!("Invalid move: {}", b as char)),
+        })
+        .collect::<Result<_>>()?;
+
+    Ok((
+        Map {
+            tiles,
+            width: width.unwrap_or(0),
+            robot: robot.ok_or_else(|| eyre!("No robot found"))?,
+        },
+        moves,
+    ))
+}
+
+fn part1((map, moves): &(Map, Vec<Direction>)) -> usize {
+    let mut map = map.clone();
+    let mut to_move = Vec::new();
+
+    for &dir in moves {
+        map.push(dir, &mut to_move);
+    }
+
+    map.gps_sum()
+}
+
+fn part2((map, moves): &(Map, Vec<Direction>)) -> usize {
+    let mut map = map.widen();
+    let mut to_move = Vec::new();
+
+    for &dir in moves {
+        map.push(dir, &mut to_move);
+    }
+
+    map.gps_sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn part1_test1() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 2028;
+        let actual = part1(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part1_test2() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 2)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 10092;
+        let actual = part1(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test1() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part2, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 618;
+        let actual = part2(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test2() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 2)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 9021;
+        let actual = part2(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+}
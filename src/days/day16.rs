@@ -0,0 +1,319 @@
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 16,
+    name: "Reindeer Maze",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part1(&data)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part2(&data)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    fn turn_left(self) -> Self {
+        match self {
+            Direction::North => Direction::West,
+            Direction::East => Direction::North,
+            Direction::South => Direction::East,
+            Direction::West => Direction::South,
+        }
+    }
+
+    fn turn_right(self) -> Self {
+        match self {
+            Direction::North => Direction::East,
+            Direction::East => Direction::South,
+            Direction::South => Direction::West,
+            Direction::West => Direction::North,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+impl Point {
+    fn step(self, dir: Direction) -> Self {
+        match dir {
+            Direction::North => Self {
+                x: self.x,
+                y: self.y.wrapping_sub(1),
+            },
+            Direction::South => Self {
+                x: self.x,
+                y: self.y.wrapping_add(1),
+            },
+            Direction::West => Self {
+                x: self.x.wrapping_sub(1),
+                y: self.y,
+            },
+            Direction::East => Self {
+                x: self.x.wrapping_add(1),
+                y: self.y,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Node {
+    pos: Point,
+    dir: Direction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct State {
+    node: Node,
+    cost: u32,
+}
+
+impl Ord for State {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        other.cost.cmp(&self.cost).then(self.node.cmp(&other.node))
+    }
+}
+
+impl PartialOrd for State {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+struct Maze {
+    walls: Vec<bool>,
+    width: usize,
+    height: usize,
+    start: Point,
+    end: Point,
+}
+
+impl Maze {
+    fn is_open(&self, p: Point) -> bool {
+        (p.x as usize) < self.width
+            && (p.y as usize) < self.height
+            && !self.walls[p.y as usize * self.width + p.x as usize]
+    }
+}
+
+fn parse(input: &str) -> Result<Maze> {
+    let mut walls = Vec::new();
+    let mut start = None;
+    let mut end = None;
+    let mut height = 0;
+
+    for (y, row) in input.trim().lines().enumerate() {
+        for (x, tile) in row.trim().bytes().enumerate() {
+            let p = Point {
+                x: x as u8,
+                y: y as u8,
+            };
+
+            match tile {
+                b'S' => start = Some(p),
+                b'E' => end = Some(p),
+                _ => {}
+            }
+
+            walls.push(tile == b'#');
+        }
+
+        height += 1;
+    }
+
+    Ok(Maze {
+        width: walls.len() / height,
+        height,
+        walls,
+        start: start.ok_or_else(|| eyre!("No start tile"))?,
+        end: end.ok_or_else(|| eyre!("No end tile"))?,
+    })
+}
+
+struct Search {
+    best: u32,
+    preds: HashMap<Node, Vec<Node>>,
+    ends: Vec<Node>,
+}
+
+fn search(maze: &Maze) -> Search {
+    let mut dists = HashMap::new();
+    let mut preds: HashMap<Node, Vec<Node>> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+    let mut best = u32::MAX;
+    let mut ends = Vec::new();
+
+    let start = Node {
+        pos: maze.start,
+        dir: Direction::East,
+    };
+    dists.insert(start, 0);
+    heap.push(State {
+        node: start,
+        cost: 0,
+    });
+
+    while let Some(cur) = heap.pop() {
+        if cur.cost > best {
+            break;
+        }
+
+        if cur.cost > dists.get(&cur.node).copied().unwrap_or(u32::MAX) {
+            continue;
+        }
+
+        if cur.node.pos == maze.end {
+            best = cur.cost;
+            ends.push(cur.node);
+            continue;
+        }
+
+        let forward = Node {
+            pos: cur.node.pos.step(cur.node.dir),
+            dir: cur.node.dir,
+        };
+        let left = Node {
+            pos: cur.node.pos,
+            dir: cur.node.dir.turn_left(),
+        };
+        let right = Node {
+            pos: cur.node.pos,
+            dir: cur.node.dir.turn_right(),
+        };
+
+        for (next, step_cost) in [(forward, 1), (left, 1000), (right, 1000)] {
+            if !maze.is_open(next.pos) {
+                continue;
+            }
+
+            let next_cost = cur.cost + step_cost;
+            let prev_cost = dists.get(&next).copied().unwrap_or(u32::MAX);
+
+            if next_cost < prev_cost {
+                dists.insert(next, next_cost);
+                preds.insert(next, vec![cur.node]);
+                heap.push(State {
+                    node: next,
+                    cost: next_cost,
+                });
+            } else if next_cost == prev_cost {
+                // Another equally good way in, which part 2 needs to know about.
+                preds.entry(next).or_default().push(cur.node);
+            }
+        }
+    }
+
+    Search { best, preds, ends }
+}
+
+fn part1(maze: &Maze) -> u32 {
+    search(maze).best
+}
+
+fn part2(maze: &Maze) -> usize {
+    let Search { preds, ends, .. } = search(maze);
+
+    let mut seen: HashSet<Node> = ends.iter().copied().collect();
+    let mut queue = ends;
+
+    while let Some(node) = queue.pop() {
+        for &pred in preds.get(&node).into_iter().flatten() {
+            if seen.insert(pred) {
+                queue.push(pred);
+            }
+        }
+    }
+
+    seen.iter().map(|n| n.pos).collect::<HashSet<_>>().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn part1_test1() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 7036;
+        let actual = part1(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part1_test2() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 2)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 11048;
+        let actual = part1(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test1() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 45;
+        let actual = part2(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test2() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 2)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 64;
+        let actual = part2(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+}
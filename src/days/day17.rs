@@ -1,17 +1,38 @@
+use std::{collections::HashSet, fmt::Display, path::Path};
+
 use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
 use color_eyre::{Report, Result, eyre::eyre};
 
+mod analyze;
+#[cfg(test)]
+mod asm;
+mod compiled;
+mod debugger;
+mod disasm;
+
 pub const DAY: Day = Day {
     day: 17,
     name: "Chronospatial Computer",
     part_1: run_part1,
-    part_2: None,
-    other: &[("Parse", run_parse)],
+    part_2: Some(run_part2),
+    other: &[
+        ("Parse", run_parse),
+        ("Trace", run_trace),
+        ("Disassemble", run_disassemble),
+        ("Analyze", run_analyze),
+        ("Interpreted", run_interpreted),
+        ("Compiled", run_compiled),
+    ],
 };
 
 fn run_part1(input: &str, b: Bench) -> BenchResult {
     let data = parse(input).map_err(UserError)?;
-    b.bench(|| Ok::<_, NoError>(part1(&data)))
+    b.bench(|| part1(&data))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| part2(&data))
 }
 
 fn run_parse(input: &str, b: Bench) -> BenchResult {
@@ -21,6 +42,77 @@ fn run_parse(input: &str, b: Bench) -> BenchResult {
     })
 }
 
+fn run_trace(input: &str, b: Bench) -> BenchResult {
+    let (pgm, machine) = parse(input).map_err(UserError)?;
+    b.bench(|| {
+        let mut vm = Vm::new(&pgm, machine.clone()).with_trace();
+        vm.run()?;
+        Ok::<_, Report>(vm.trace().len())
+    })
+}
+
+fn run_disassemble(input: &str, b: Bench) -> BenchResult {
+    let (pgm, _) = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(disasm::disassemble(&pgm)))
+}
+
+fn run_analyze(input: &str, b: Bench) -> BenchResult {
+    let (pgm, _) = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, Report>(analyze::analyze(&pgm)?.to_string()))
+}
+
+/// Runs the debugger on the program in `path`, reading commands from stdin until it ends or
+/// `quit` is entered, then prints everything the program output.
+pub fn run_debugger(path: &Path) -> Result<()> {
+    let input = std::fs::read_to_string(path)?;
+    let (pgm, machine) = parse(&input)?;
+
+    let mut debugger = debugger::Debugger::new(Vm::new(&pgm, machine));
+    debugger.run(std::io::stdin().lock(), std::io::stdout().lock())?;
+    println!("output: {}", debugger.output());
+
+    Ok(())
+}
+
+/// How many values of A the interpreted and compiled benches run the program with, to mimic the
+/// repeated runs of the part 2 search. The compiled runner doesn't look for loops, so the
+/// interpreter runs without loop detection too, and the benches only differ in decoding.
+const BENCH_RUNS: u64 = 1024;
+
+fn run_interpreted(input: &str, b: Bench) -> BenchResult {
+    let (pgm, machine) = parse(input).map_err(UserError)?;
+    b.bench(|| {
+        let mut vm = Vm::new(&pgm, machine.clone()).without_loop_detection();
+        let mut outputs = 0;
+        for a in 0..BENCH_RUNS {
+            vm.reset(Machine {
+                reg: [a, machine.reg[1], machine.reg[2]],
+                ..machine
+            });
+            outputs += vm.run()?.len();
+        }
+
+        Ok::<_, Report>(outputs)
+    })
+}
+
+fn run_compiled(input: &str, b: Bench) -> BenchResult {
+    let (pgm, machine) = parse(input).map_err(UserError)?;
+    b.bench(|| {
+        let compiled = compiled::Compiled::new(&pgm);
+        let mut outputs = 0;
+        for a in 0..BENCH_RUNS {
+            let machine = Machine {
+                reg: [a, machine.reg[1], machine.reg[2]],
+                ..machine
+            };
+            outputs += compiled.run(&machine)?.len();
+        }
+
+        Ok::<_, Report>(outputs)
+    })
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum OpCode {
     Adv,
@@ -47,6 +139,19 @@ impl OpCode {
             _ => None,
         }
     }
+
+    fn mnemonic(self) -> &'static str {
+        match self {
+            OpCode::Adv => "adv",
+            OpCode::Bxl => "bxl",
+            OpCode::Bst => "bst",
+            OpCode::Jnz => "jnz",
+            OpCode::Bxc => "bxc",
+            OpCode::Out => "out",
+            OpCode::Bdv => "bdv",
+            OpCode::Cdv => "cdv",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -63,18 +168,34 @@ impl Combo {
             _ => None,
         }
     }
+
+    fn value(self, reg: &[u64; 3]) -> u64 {
+        match self {
+            Combo::Reg(i) => reg[i as usize],
+            Combo::Imm(i) => i as u64,
+        }
+    }
+}
+
+impl Display for Combo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Combo::Reg(i) => write!(f, "{}", (b'A' + i) as char),
+            Combo::Imm(i) => write!(f, "{i}"),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 struct Machine {
-    ip: u8,
-    reg: [u32; 3],
+    ip: usize,
+    reg: [u64; 3],
 }
 
 impl Machine {
     fn read_op(&mut self, pgm: &[u8]) -> Option<OpCode> {
         let next = pgm
-            .get(self.ip as usize)
+            .get(self.ip)
             .and_then(|&opcode| OpCode::from_int(opcode));
 
         self.ip += 1;
@@ -82,20 +203,53 @@ impl Machine {
     }
 
     fn read_literal(&mut self, pgm: &[u8]) -> Option<u8> {
-        let next = pgm.get(self.ip as usize).copied();
+        let next = pgm.get(self.ip).copied();
 
         self.ip += 1;
         next
     }
+}
 
-    fn read_combi(&mut self, pgm: &[u8]) -> Option<Combo> {
-        let next = pgm
-            .get(self.ip as usize)
-            .and_then(|&val| Combo::from_int(val));
+/// Checks that every instruction can be executed, so a bad program is reported rather than
+/// silently halting part way through.
+fn validate(pgm: &[u8]) -> Result<()> {
+    let instrs = pgm.chunks_exact(2);
+    if let &[opcode] = instrs.remainder() {
+        return Err(eyre!(
+            "Instruction {}: opcode {opcode} has no operand",
+            pgm.len() / 2
+        ));
+    }
 
-        self.ip += 1;
-        next
+    for (idx, instr) in instrs.enumerate() {
+        let &[opcode, operand] = instr else {
+            unreachable!()
+        };
+
+        let Some(opcode) = OpCode::from_int(opcode) else {
+            return Err(eyre!("Instruction {idx}: invalid opcode {opcode}"));
+        };
+
+        match opcode {
+            OpCode::Adv | OpCode::Bst | OpCode::Out | OpCode::Bdv | OpCode::Cdv
+                if Combo::from_int(operand).is_none() =>
+            {
+                return Err(eyre!(
+                    "Instruction {idx}: invalid combo operand {operand} for {}",
+                    opcode.mnemonic()
+                ));
+            }
+            _ if operand > 7 => {
+                return Err(eyre!(
+                    "Instruction {idx}: operand {operand} for {} is out of range",
+                    opcode.mnemonic()
+                ));
+            }
+            _ => {}
+        }
     }
+
+    Ok(())
 }
 
 fn parse(input: &str) -> Result<(Vec<u8>, Machine)> {
@@ -105,100 +259,321 @@ fn parse(input: &str) -> Result<(Vec<u8>, Machine)> {
         .split_once("\n\n")
         .ok_or_else(|| eyre!("Invalid input"))?;
 
-    for (i, line) in regs.trim().lines().enumerate() {
-        let Some((_, val)) = line.split_once(':') else {
+    let mut reg_lines = regs.trim().lines();
+    for (reg, name) in machine.reg.iter_mut().zip(["A", "B", "C"]) {
+        let line = reg_lines
+            .next()
+            .ok_or_else(|| eyre!("Missing register {name}"))?;
+        let Some((label, val)) = line.split_once(':') else {
             return Err(eyre!("Invalid register line: {}", line));
         };
 
-        machine.reg[i] = val.trim().parse()?;
+        if label.trim() != format!("Register {name}") {
+            return Err(eyre!("Expected register {name}, found `{}`", label.trim()));
+        }
+
+        *reg = val.trim().parse()?;
+    }
+
+    if let Some(line) = reg_lines.next() {
+        return Err(eyre!("Unexpected register line: {}", line));
     }
 
-    let ops = pgm
+    let ops: Vec<u8> = pgm
         .split_once(':')
         .ok_or_else(|| eyre!("Invalid program"))?
         .1
         .trim()
         .split(',')
-        .map(str::parse::<u8>)
-        .collect::<Result<_, _>>()?;
+        .enumerate()
+        .map(|(i, val)| {
+            val.trim()
+                .parse::<u8>()
+                .map_err(|_| eyre!("Instruction {}: invalid value `{val}`", i / 2))
+        })
+        .collect::<Result<_>>()?;
+
+    validate(&ops)?;
 
     Ok((ops, machine))
 }
 
-fn part1((pgm, machine): &(Vec<u8>, Machine)) -> String {
-    let mut machine = machine.clone();
-    let mut output = String::new();
+const STEP_LIMIT: usize = 1_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum VmError {
+    StepLimit { steps: usize },
+    Loop { ip: usize },
+}
+
+impl Display for VmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            VmError::StepLimit { steps } => write!(f, "program did not halt after {steps} steps"),
+            VmError::Loop { ip } => write!(f, "loop detected at ip {ip}"),
+        }
+    }
+}
+
+impl std::error::Error for VmError {}
+
+/// The state of the registers before an instruction was executed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TraceEntry {
+    ip: usize,
+    opcode: OpCode,
+    operand: u8,
+    reg: [u64; 3],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    Continue,
+    Output(u8),
+    Halted,
+}
+
+#[derive(Debug, Clone)]
+struct Vm {
+    program: Vec<u8>,
+    machine: Machine,
+    halted: bool,
+    trace: Option<Vec<TraceEntry>>,
+    budget: usize,
+    steps: usize,
+    /// The machine state every time a jump was taken. The machine is deterministic, so seeing the
+    /// same state twice means it will never halt.
+    jump_states: HashSet<(usize, [u64; 3])>,
+    detect_loops: bool,
+}
+
+impl Vm {
+    fn new(program: &[u8], machine: Machine) -> Self {
+        Self {
+            program: program.to_owned(),
+            machine,
+            halted: false,
+            trace: None,
+            budget: STEP_LIMIT,
+            steps: 0,
+            jump_states: HashSet::new(),
+            detect_loops: true,
+        }
+    }
+
+    /// Sets how many instructions may be executed before giving up.
+    fn with_budget(mut self, budget: usize) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Stops looking for repeated states, leaving only the step budget to catch a program that
+    /// never halts.
+    fn without_loop_detection(mut self) -> Self {
+        self.detect_loops = false;
+        self
+    }
+
+    fn with_trace(mut self) -> Self {
+        self.trace = Some(Vec::new());
+        self
+    }
+
+    fn trace(&self) -> &[TraceEntry] {
+        self.trace.as_deref().unwrap_or(&[])
+    }
+
+    /// Restarts the program with a new machine state, keeping the program and trace setting.
+    fn reset(&mut self, machine: Machine) {
+        self.machine = machine;
+        self.halted = false;
+        self.steps = 0;
+        self.jump_states.clear();
+        if let Some(trace) = &mut self.trace {
+            trace.clear();
+        }
+    }
+
+    fn step(&mut self) -> Result<Step, VmError> {
+        if self.halted {
+            return Ok(Step::Halted);
+        }
+
+        if self.steps >= self.budget {
+            return Err(VmError::StepLimit { steps: self.steps });
+        }
+        self.steps += 1;
+
+        let ip = self.machine.ip;
+        let Some(opcode) = self.machine.read_op(&self.program) else {
+            self.halted = true;
+            return Ok(Step::Halted);
+        };
+
+        // `bxc` ignores its operand, so it still runs if the program ends before the operand.
+        let operand = match (opcode, self.machine.read_literal(&self.program)) {
+            (_, Some(operand)) => operand,
+            (OpCode::Bxc, None) => 0,
+            (_, None) => {
+                self.halted = true;
+                return Ok(Step::Halted);
+            }
+        };
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceEntry {
+                ip,
+                opcode,
+                operand,
+                reg: self.machine.reg,
+            });
+        }
+
+        let reg = &mut self.machine.reg;
+        let combo = Combo::from_int(operand).map(|c| c.value(reg));
 
-    while let Some(opcode) = machine.read_op(pgm) {
         match opcode {
             OpCode::Adv | OpCode::Bdv | OpCode::Cdv => {
-                let Some(operand) = machine.read_combi(pgm) else {
-                    break;
+                let Some(shift) = combo else {
+                    self.halted = true;
+                    return Ok(Step::Halted);
                 };
 
-                let divisor = match operand {
-                    Combo::Reg(i) => machine.reg[i as usize],
-                    Combo::Imm(i) => i as u32,
-                };
-
-                let reg = match opcode {
+                let reg_idx = match opcode {
                     OpCode::Adv => 0,
                     OpCode::Bdv => 1,
                     OpCode::Cdv => 2,
                     _ => unreachable!(),
                 };
-                machine.reg[reg] = machine.reg[0] / u32::pow(2, divisor);
-            }
-            OpCode::Bxl => {
-                let Some(operand) = machine.read_literal(pgm) else {
-                    break;
-                };
 
-                machine.reg[1] ^= operand as u32;
+                // Dividing by 2^shift, but a shift of 64 or more would overflow.
+                reg[reg_idx] = if shift < 64 { reg[0] >> shift } else { 0 };
             }
+            OpCode::Bxl => reg[1] ^= operand as u64,
             OpCode::Bst => {
-                let Some(operand) = machine.read_combi(pgm) else {
-                    break;
+                let Some(value) = combo else {
+                    self.halted = true;
+                    return Ok(Step::Halted);
                 };
 
-                let op_value = match operand {
-                    Combo::Reg(i) => machine.reg[i as usize],
-                    Combo::Imm(i) => i as u32,
-                };
-
-                machine.reg[1] = op_value & 0x7;
+                reg[1] = value & 0x7;
             }
             OpCode::Jnz => {
-                let Some(operand) = machine.read_literal(pgm) else {
-                    break;
-                };
-
-                if machine.reg[0] != 0 {
-                    machine.ip = operand;
+                if reg[0] != 0 {
+                    let target = operand as usize;
+                    if self.detect_loops && !self.jump_states.insert((target, *reg)) {
+                        // The program can never halt, so don't let it carry on past the jump.
+                        self.halted = true;
+                        return Err(VmError::Loop { ip: target });
+                    }
+
+                    self.machine.ip = target;
                 }
             }
-            OpCode::Bxc => {
-                let _ = machine.read_literal(pgm);
-                machine.reg[1] ^= machine.reg[2];
-            }
+            OpCode::Bxc => reg[1] ^= reg[2],
             OpCode::Out => {
-                let Some(operand) = machine.read_combi(pgm) else {
-                    break;
+                let Some(value) = combo else {
+                    self.halted = true;
+                    return Ok(Step::Halted);
                 };
 
-                let op_value = match operand {
-                    Combo::Reg(i) => machine.reg[i as usize],
-                    Combo::Imm(i) => i as u32,
-                };
+                return Ok(Step::Output((value & 0x7) as u8));
+            }
+        }
 
-                output.push(((op_value & 0x7) as u8 + b'0') as char);
-                output.push(',');
+        Ok(Step::Continue)
+    }
+
+    /// Runs until the next output, or returns `None` if the program halts first.
+    fn run_until_output(&mut self) -> Result<Option<u8>, VmError> {
+        loop {
+            match self.step()? {
+                Step::Continue => {}
+                Step::Output(val) => return Ok(Some(val)),
+                Step::Halted => return Ok(None),
             }
         }
     }
 
-    output.pop(); // remove trailing comma
-    output
+    /// Runs the program to completion.
+    fn run(&mut self) -> Result<Vec<u8>, VmError> {
+        let mut output = Vec::new();
+        while let Some(val) = self.run_until_output()? {
+            output.push(val);
+        }
+
+        Ok(output)
+    }
+}
+
+fn format_output(output: &[u8]) -> String {
+    let mut output_str = String::new();
+    for &val in output {
+        output_str.push((val + b'0') as char);
+        output_str.push(',');
+    }
+
+    output_str.pop(); // remove trailing comma
+    output_str
+}
+
+fn part1((pgm, machine): &(Vec<u8>, Machine)) -> Result<String> {
+    let output = Vm::new(pgm, machine.clone()).run()?;
+    Ok(format_output(&output))
+}
+
+fn part2((pgm, machine): &(Vec<u8>, Machine)) -> Result<u64> {
+    let shape = analyze::analyze(pgm)?;
+    if pgm.len() * shape.shift as usize > u64::BITS as usize {
+        return Err(eyre!(
+            "A would need more than 64 bits to output {} values",
+            pgm.len()
+        ));
+    }
+
+    // Each loop iteration consumes the low bits of A, and the last output only depends on the
+    // highest bits, so we can build A from the end of the program backwards. A candidate for
+    // `pgm[i..]` shifted right once is a candidate for `pgm[i + 1..]`, which already outputs the
+    // rest of the program, so only the first output needs checking.
+    let mut candidates = vec![0];
+    let mut next_candidates = Vec::new();
+
+    for &expected in pgm.iter().rev() {
+        next_candidates.clear();
+
+        for &a in &candidates {
+            for bits in 0..1 << shape.shift {
+                let next_a = (a << shape.shift) | bits;
+                // The loop halts once A is zero, so zero can only ever output a single value.
+                if next_a != 0 && shape.output(next_a) == expected {
+                    next_candidates.push(next_a);
+                }
+            }
+        }
+
+        std::mem::swap(&mut candidates, &mut next_candidates);
+    }
+
+    let a = candidates
+        .into_iter()
+        .min()
+        .ok_or_else(|| eyre!("No value of A makes the program output itself"))?;
+
+    // The analysis only looks at one loop iteration, so check the answer against the real thing.
+    // The loop runs once per output, so a quine never needs more steps than this.
+    let mut machine = machine.clone();
+    machine.reg[0] = a;
+    let output = Vm::new(pgm, machine)
+        .with_budget(pgm.len() * pgm.len())
+        .run()?;
+    if output != *pgm {
+        return Err(eyre!(
+            "A = {a} should output the program, but outputs {}",
+            format_output(&output)
+        ));
+    }
+
+    Ok(a)
 }
 
 #[cfg(test)]
@@ -227,8 +602,209 @@ mod tests {
 
         let parsed = parse(&data).unwrap();
         let expected = "4,6,3,5,6,3,5,2,1,0";
-        let actual = part1(&parsed);
+        let actual = part1(&parsed).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn parse_error_test() {
+        let cases = [
+            (
+                "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,8,3",
+                "Instruction 1: invalid opcode 8",
+            ),
+            (
+                "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,7",
+                "Instruction 1: invalid combo operand 7 for out",
+            ),
+            (
+                "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,9",
+                "Instruction 0: operand 9 for bxl is out of range",
+            ),
+            (
+                "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5",
+                "Instruction 1: opcode 5 has no operand",
+            ),
+            (
+                "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,x",
+                "Instruction 1: invalid value `x`",
+            ),
+            (
+                "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,300",
+                "Instruction 1: invalid value `300`",
+            ),
+            (
+                "Register A: 1\nRegister B: 0\n\nProgram: 0,1",
+                "Missing register C",
+            ),
+            (
+                "Register A: 1\nRegister C: 0\nRegister B: 0\n\nProgram: 0,1",
+                "Expected register B, found `Register C`",
+            ),
+            (
+                "Register A: 1\nRegister B: 0\nRegister C: 0\nRegister D: 0\n\nProgram: 0,1",
+                "Unexpected register line: Register D: 0",
+            ),
+        ];
+
+        for (input, expected) in cases {
+            let actual = parse(input).unwrap_err().to_string();
+            assert_eq!(expected, actual, "{input}");
+        }
+    }
+
+    #[test]
+    fn trace_test() {
+        let machine = Machine {
+            ip: 0,
+            reg: [2024, 0, 0],
+        };
+        let mut vm = Vm::new(&[0, 1, 5, 4, 3, 0], machine).with_trace();
+
+        assert_eq!(vm.step(), Ok(Step::Continue));
+        assert_eq!(vm.step(), Ok(Step::Output(4)));
+        assert_eq!(vm.step(), Ok(Step::Continue));
+        assert_eq!(
+            vm.trace(),
+            [
+                TraceEntry {
+                    ip: 0,
+                    opcode: OpCode::Adv,
+                    operand: 1,
+                    reg: [2024, 0, 0],
+                },
+                TraceEntry {
+                    ip: 2,
+                    opcode: OpCode::Out,
+                    operand: 4,
+                    reg: [1012, 0, 0],
+                },
+                TraceEntry {
+                    ip: 4,
+                    opcode: OpCode::Jnz,
+                    operand: 0,
+                    reg: [1012, 0, 0],
+                },
+            ]
+        );
+        assert_eq!(vm.machine.ip, 0);
+    }
+
+    #[test]
+    fn run_test() {
+        let machine = Machine {
+            ip: 0,
+            reg: [10, 0, 0],
+        };
+
+        let mut vm = Vm::new(&[5, 0, 5, 1, 5, 4], machine.clone());
+        assert_eq!(vm.run(), Ok(vec![0, 1, 2]));
+
+        let mut vm = Vm::new(&[5, 0, 5, 1, 5, 4], machine.clone()).with_budget(2);
+        assert_eq!(vm.run(), Err(VmError::StepLimit { steps: 2 }));
+
+        // A trailing `bxc` runs without its operand, then the program halts.
+        let mut vm = Vm::new(
+            &[4],
+            Machine {
+                ip: 0,
+                reg: [0, 1, 2],
+            },
+        );
+        assert_eq!(vm.step(), Ok(Step::Continue));
+        assert_eq!(vm.machine.reg, [0, 3, 2]);
+        assert_eq!(vm.step(), Ok(Step::Halted));
+    }
+
+    #[test]
+    fn loop_test() {
+        // Never halts, as A is never changed.
+        let machine = Machine {
+            ip: 0,
+            reg: [10, 0, 0],
+        };
+        let mut vm = Vm::new(&[5, 4, 3, 0], machine.clone());
+        assert_eq!(vm.run(), Err(VmError::Loop { ip: 0 }));
+
+        // B cycles through a few values before the state repeats.
+        let mut vm = Vm::new(&[1, 1, 4, 0, 3, 0], machine.clone());
+        assert_eq!(vm.run(), Err(VmError::Loop { ip: 0 }));
+        assert_eq!(vm.steps, 9);
+
+        // Without loop detection, only the step budget stops it.
+        let mut vm = Vm::new(&[5, 4, 3, 0], machine)
+            .without_loop_detection()
+            .with_budget(100);
+        assert_eq!(vm.run(), Err(VmError::StepLimit { steps: 100 }));
+
+        // Once a loop is found, the VM stays stopped rather than falling through the jump.
+        let mut vm = Vm::new(
+            &[5, 4, 3, 0, 5, 5],
+            Machine {
+                ip: 0,
+                reg: [1, 0, 0],
+            },
+        );
+        assert_eq!(vm.run(), Err(VmError::Loop { ip: 0 }));
+        assert_eq!(vm.step(), Ok(Step::Halted));
+
+        let err = part1(&(
+            vec![5, 4, 3, 0],
+            Machine {
+                ip: 0,
+                reg: [1, 0, 0],
+            },
+        ))
+        .unwrap_err();
+        assert_eq!(err.to_string(), "loop detected at ip 0");
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part2, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 117440;
+        let actual = part2(&parsed).unwrap();
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn part2_shape_test() {
+        let machine = Machine {
+            ip: 0,
+            reg: [0, 0, 0],
+        };
+
+        // Reads B before writing it, so it doesn't fit the shape the search relies on.
+        let pgm = vec![1, 3, 0, 3, 5, 5, 3, 0];
+        let expected = analyze::analyze(&pgm).unwrap_err().to_string();
+        let actual = part2(&(pgm, machine)).unwrap_err().to_string();
+
+        assert_eq!(
+            "Instruction 0: reads B before writing it, so the output depends on the previous loop",
+            expected
+        );
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_no_quine_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        // This has the right shape, but each output overlaps the next by two bits, so no value of A
+        // can output the program.
+        let parsed = parse(&data).unwrap();
+        let actual = part2(&parsed).unwrap_err().to_string();
+
+        assert_eq!("No value of A makes the program output itself", actual);
+    }
 }
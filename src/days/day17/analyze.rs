@@ -0,0 +1,307 @@
+use std::fmt::Display;
+
+use color_eyre::{Result, eyre::eyre};
+
+use super::{Combo, compiled::Instr};
+
+/// A value computed by one loop iteration, in terms of the value A had when the iteration began.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    A,
+    Lit(u64),
+    Xor(Box<Expr>, Box<Expr>),
+    Shr(Box<Expr>, Box<Expr>),
+    Mod8(Box<Expr>),
+}
+
+impl Expr {
+    fn xor(lhs: Expr, rhs: Expr) -> Expr {
+        match (lhs, rhs) {
+            (lhs, Expr::Lit(0)) => lhs,
+            (Expr::Lit(x), Expr::Lit(y)) => Expr::Lit(x ^ y),
+            // Fold chains of `bxl`, which are common in puzzle inputs.
+            (Expr::Xor(inner, lit), Expr::Lit(y)) => match *lit {
+                Expr::Lit(x) => Expr::xor(*inner, Expr::Lit(x ^ y)),
+                lit => Expr::Xor(
+                    Box::new(Expr::Xor(inner, Box::new(lit))),
+                    Box::new(Expr::Lit(y)),
+                ),
+            },
+            (lhs, rhs) => Expr::Xor(Box::new(lhs), Box::new(rhs)),
+        }
+    }
+
+    fn shr(lhs: Expr, rhs: Expr) -> Expr {
+        Expr::Shr(Box::new(lhs), Box::new(rhs))
+    }
+
+    fn mod8(expr: Expr) -> Expr {
+        match expr {
+            Expr::Lit(x) => Expr::Lit(x & 0x7),
+            Expr::Mod8(_) => expr,
+            expr => Expr::Mod8(Box::new(expr)),
+        }
+    }
+
+    fn eval(&self, a: u64) -> u64 {
+        match self {
+            Expr::A => a,
+            Expr::Lit(x) => *x,
+            Expr::Xor(lhs, rhs) => lhs.eval(a) ^ rhs.eval(a),
+            Expr::Shr(lhs, rhs) => {
+                let shift = rhs.eval(a);
+                if shift < 64 { lhs.eval(a) >> shift } else { 0 }
+            }
+            Expr::Mod8(expr) => expr.eval(a) & 0x7,
+        }
+    }
+}
+
+/// Shows an operand of a larger expression, in parentheses unless it can't be misread.
+struct Operand<'a>(&'a Expr);
+
+impl Display for Operand<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Expr::A | Expr::Lit(_) => write!(f, "{}", self.0),
+            expr => write!(f, "({expr})"),
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expr::A => write!(f, "A"),
+            Expr::Lit(x) => write!(f, "{x}"),
+            Expr::Xor(lhs, rhs) => {
+                // Xor is associative, so nested xors don't need parentheses.
+                let side = |expr: &Expr, f: &mut std::fmt::Formatter<'_>| match expr {
+                    Expr::Xor(..) => write!(f, "{expr}"),
+                    expr => write!(f, "{}", Operand(expr)),
+                };
+
+                side(lhs, f)?;
+                write!(f, " ^ ")?;
+                side(rhs, f)
+            }
+            Expr::Shr(lhs, rhs) => write!(f, "{} >> {}", Operand(lhs), Operand(rhs)),
+            Expr::Mod8(expr) => write!(f, "{} & 7", Operand(expr)),
+        }
+    }
+}
+
+/// A program that is a single loop, which outputs a function of A and then shifts A right by a
+/// constant, until A is zero.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct LoopShape {
+    /// The value output by each iteration.
+    output: Expr,
+    /// How far A is shifted right by each iteration.
+    pub(super) shift: u32,
+}
+
+impl LoopShape {
+    /// The value output by an iteration that starts with `a` in register A.
+    pub(super) fn output(&self, a: u64) -> u8 {
+        self.output.eval(a) as u8
+    }
+}
+
+impl Display for LoopShape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "out = {}", self.output)?;
+        write!(f, "A = A >> {}", self.shift)
+    }
+}
+
+fn read(regs: &[Option<Expr>; 3], idx: usize, reg: u8) -> Result<Expr> {
+    regs[reg as usize].clone().ok_or_else(|| {
+        eyre!(
+            "Instruction {idx}: reads {} before writing it, so the output depends on the previous loop",
+            Combo::Reg(reg)
+        )
+    })
+}
+
+fn read_combo(regs: &[Option<Expr>; 3], idx: usize, combo: Combo) -> Result<Expr> {
+    match combo {
+        Combo::Reg(reg) => read(regs, idx, reg),
+        Combo::Imm(i) => Ok(Expr::Lit(i as u64)),
+    }
+}
+
+/// Works out what each iteration of the program's loop outputs, or explains why the program
+/// isn't a loop of that shape.
+pub(super) fn analyze(pgm: &[u8]) -> Result<LoopShape> {
+    let instrs = (0..pgm.len())
+        .step_by(2)
+        .map(|ip| Instr::decode(pgm, ip).ok_or_else(|| eyre!("Instruction {}: invalid", ip / 2)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let Some((Instr::Jnz(0), body)) = instrs.split_last() else {
+        return Err(eyre!(
+            "Program does not end with `jnz 0`, so it is not a single loop"
+        ));
+    };
+
+    // B and C start unknown, since they hold whatever the previous iteration left in them.
+    let mut regs = [Some(Expr::A), None, None];
+    let mut shift = None;
+    let mut output = None;
+
+    for (idx, &instr) in body.iter().enumerate() {
+        match instr {
+            Instr::Div {
+                dst: 0,
+                shift: amount,
+            } => {
+                if shift.is_some() {
+                    return Err(eyre!(
+                        "Instruction {idx}: A is shifted more than once per loop"
+                    ));
+                }
+
+                let Combo::Imm(amount @ 1..) = amount else {
+                    return Err(eyre!(
+                        "Instruction {idx}: `adv {amount}` does not shift A by a non-zero constant"
+                    ));
+                };
+
+                shift = Some(amount as u32);
+                regs[0] = Some(Expr::shr(read(&regs, idx, 0)?, Expr::Lit(amount as u64)));
+            }
+            Instr::Div { dst, shift: amount } => {
+                let value = Expr::shr(read(&regs, idx, 0)?, read_combo(&regs, idx, amount)?);
+                regs[dst] = Some(value);
+            }
+            Instr::Bxl(literal) => {
+                regs[1] = Some(Expr::xor(read(&regs, idx, 1)?, Expr::Lit(literal)));
+            }
+            Instr::Bst(combo) => regs[1] = Some(Expr::mod8(read_combo(&regs, idx, combo)?)),
+            Instr::Jnz(_) => {
+                return Err(eyre!("Instruction {idx}: jumps before the end of the loop"));
+            }
+            Instr::Bxc => regs[1] = Some(Expr::xor(read(&regs, idx, 1)?, read(&regs, idx, 2)?)),
+            Instr::Out(combo) => {
+                if output.is_some() {
+                    return Err(eyre!("Instruction {idx}: outputs more than once per loop"));
+                }
+
+                output = Some(Expr::mod8(read_combo(&regs, idx, combo)?));
+            }
+        }
+    }
+
+    Ok(LoopShape {
+        output: output.ok_or_else(|| eyre!("Program never outputs anything"))?,
+        shift: shift.ok_or_else(|| eyre!("Program never shifts A, so the loop never ends"))?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day17::{Machine, compiled::Compiled};
+
+    #[test]
+    fn describe_test() {
+        let cases: &[(&[u8], &str)] = &[
+            (&[0, 3, 5, 4, 3, 0], "out = (A >> 3) & 7\nA = A >> 3"),
+            (
+                &[2, 4, 1, 1, 7, 5, 1, 5, 4, 0, 0, 3, 5, 5, 3, 0],
+                "out = ((A & 7) ^ 4 ^ (A >> ((A & 7) ^ 1))) & 7\nA = A >> 3",
+            ),
+            (
+                &[2, 4, 1, 3, 7, 5, 0, 3, 1, 5, 4, 4, 5, 5, 3, 0],
+                "out = ((A & 7) ^ 6 ^ (A >> ((A & 7) ^ 3))) & 7\nA = A >> 3",
+            ),
+            (&[0, 1, 5, 4, 3, 0], "out = (A >> 1) & 7\nA = A >> 1"),
+        ];
+
+        for &(pgm, expected) in cases {
+            assert_eq!(expected, analyze(pgm).unwrap().to_string(), "{pgm:?}");
+        }
+    }
+
+    #[test]
+    fn output_test() {
+        let programs: &[&[u8]] = &[
+            &[0, 3, 5, 4, 3, 0],
+            &[2, 4, 1, 1, 7, 5, 1, 5, 4, 0, 0, 3, 5, 5, 3, 0],
+            &[2, 4, 1, 3, 7, 5, 0, 3, 1, 5, 4, 4, 5, 5, 3, 0],
+            &[2, 4, 1, 0, 6, 5, 1, 7, 7, 4, 4, 6, 0, 2, 5, 5, 3, 0],
+        ];
+
+        for pgm in programs {
+            let shape = analyze(pgm).unwrap();
+            let compiled = Compiled::new(pgm);
+            for a in 0..4096 {
+                let machine = Machine {
+                    ip: 0,
+                    reg: [a, 0, 0],
+                };
+                let mut first = None;
+                compiled
+                    .run_with(&machine, |val| {
+                        first = Some(val);
+                        false
+                    })
+                    .unwrap();
+
+                assert_eq!(first, Some(shape.output(a)), "{pgm:?} with A = {a}");
+            }
+        }
+    }
+
+    #[test]
+    fn error_test() {
+        let cases: &[(&[u8], &str)] = &[
+            (
+                &[0, 3, 5, 4],
+                "Program does not end with `jnz 0`, so it is not a single loop",
+            ),
+            (
+                &[0, 3, 5, 4, 3, 2],
+                "Program does not end with `jnz 0`, so it is not a single loop",
+            ),
+            (
+                &[0, 3, 3, 0, 5, 4, 3, 0],
+                "Instruction 1: jumps before the end of the loop",
+            ),
+            (
+                &[0, 1, 0, 2, 5, 4, 3, 0],
+                "Instruction 1: A is shifted more than once per loop",
+            ),
+            (
+                &[0, 5, 5, 4, 3, 0],
+                "Instruction 0: `adv B` does not shift A by a non-zero constant",
+            ),
+            (
+                &[0, 0, 5, 4, 3, 0],
+                "Instruction 0: `adv 0` does not shift A by a non-zero constant",
+            ),
+            (
+                &[0, 3, 5, 4, 5, 4, 3, 0],
+                "Instruction 2: outputs more than once per loop",
+            ),
+            (
+                &[1, 3, 0, 3, 5, 5, 3, 0],
+                "Instruction 0: reads B before writing it, so the output depends on the previous loop",
+            ),
+            (
+                &[2, 4, 4, 0, 0, 3, 5, 5, 3, 0],
+                "Instruction 1: reads C before writing it, so the output depends on the previous loop",
+            ),
+            (&[0, 3, 3, 0], "Program never outputs anything"),
+            (
+                &[5, 4, 3, 0],
+                "Program never shifts A, so the loop never ends",
+            ),
+        ];
+
+        for &(pgm, expected) in cases {
+            assert_eq!(expected, analyze(pgm).unwrap_err().to_string(), "{pgm:?}");
+        }
+    }
+}
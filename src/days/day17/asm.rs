@@ -0,0 +1,190 @@
+use std::collections::HashMap;
+
+use color_eyre::{Result, eyre::eyre};
+
+use super::OpCode;
+
+fn parse_opcode(mnemonic: &str) -> Option<OpCode> {
+    (0..8)
+        .filter_map(OpCode::from_int)
+        .find(|op| op.mnemonic() == mnemonic)
+}
+
+fn parse_combo(operand: &str) -> Result<u8, String> {
+    match operand {
+        "A" => Ok(4),
+        "B" => Ok(5),
+        "C" => Ok(6),
+        _ => match operand.parse::<u8>() {
+            Ok(i @ 0..=3) => Ok(i),
+            Ok(7) => Err("combo operand 7 is reserved".to_owned()),
+            Ok(i) => Err(format!(
+                "combo operand {i} out of range, immediates must be 0-3, or a register A, B, or C"
+            )),
+            Err(_) => Err(format!("invalid combo operand `{operand}`")),
+        },
+    }
+}
+
+fn parse_literal(operand: &str) -> Result<u8, String> {
+    match operand.parse::<u8>() {
+        Ok(i @ 0..=7) => Ok(i),
+        Ok(i) => Err(format!("literal {i} out of range, must be 0-7")),
+        Err(_) => Err(format!("invalid literal `{operand}`")),
+    }
+}
+
+/// Assembles the mnemonic syntax produced by the disassembler back into a program.
+///
+/// Each line holds an optional `label:`, then an optional instruction. Anything after a `;` is a
+/// comment. `jnz` accepts either a label or a literal address.
+pub(super) fn assemble(src: &str) -> Result<Vec<u8>> {
+    struct Line<'a> {
+        line_no: usize,
+        mnemonic: &'a str,
+        operand: Option<&'a str>,
+    }
+
+    let mut labels = HashMap::new();
+    let mut lines = Vec::new();
+
+    for (line_no, line) in (1..).zip(src.lines()) {
+        let line = line.split_once(';').map_or(line, |(code, _)| code).trim();
+
+        let line = match line.split_once(':') {
+            Some((label, rest)) => {
+                let label = label.trim();
+                if label.is_empty() || label.contains(char::is_whitespace) {
+                    return Err(eyre!("line {line_no}: invalid label `{label}`"));
+                }
+
+                let address = lines.len() * 2;
+                if labels.insert(label, address).is_some() {
+                    return Err(eyre!("line {line_no}: duplicate label `{label}`"));
+                }
+
+                rest.trim()
+            }
+            None => line,
+        };
+
+        let mut parts = line.split_ascii_whitespace();
+        let Some(mnemonic) = parts.next() else {
+            continue;
+        };
+
+        let operand = parts.next();
+        if parts.next().is_some() {
+            return Err(eyre!("line {line_no}: too many operands"));
+        }
+
+        lines.push(Line {
+            line_no,
+            mnemonic,
+            operand,
+        });
+    }
+
+    let mut pgm = Vec::with_capacity(lines.len() * 2);
+
+    for Line {
+        line_no,
+        mnemonic,
+        operand,
+    } in lines
+    {
+        let opcode = parse_opcode(mnemonic)
+            .ok_or_else(|| eyre!("line {line_no}: unknown instruction `{mnemonic}`"))?;
+
+        let operand = match (opcode, operand) {
+            (OpCode::Bxc, None) => Ok(0),
+            (_, None) => Err(format!("`{mnemonic}` needs an operand")),
+            (OpCode::Adv | OpCode::Bst | OpCode::Out | OpCode::Bdv | OpCode::Cdv, Some(op)) => {
+                parse_combo(op)
+            }
+            // Jumping to an odd address is allowed, it reads the program with a different
+            // alignment.
+            (OpCode::Jnz, Some(op)) if op.starts_with(|c: char| c.is_ascii_digit()) => {
+                parse_literal(op)
+            }
+            (OpCode::Jnz, Some(label)) => match labels.get(label) {
+                Some(&address) if address <= 7 => Ok(address as u8),
+                Some(address) => Err(format!(
+                    "label `{label}` is at {address}, beyond the reach of a 3-bit jump"
+                )),
+                None => Err(format!("unknown label `{label}`")),
+            },
+            (OpCode::Bxl | OpCode::Bxc, Some(op)) => parse_literal(op),
+        }
+        .map_err(|e| eyre!("line {line_no}: {e}"))?;
+
+        pgm.push(opcode as u8);
+        pgm.push(operand);
+    }
+
+    Ok(pgm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day17::{Machine, Vm, disasm::disassemble};
+
+    #[test]
+    fn round_trip_test() {
+        let programs: &[&[u8]] = &[
+            &[2, 4, 1, 5, 7, 5, 4, 3, 1, 6, 0, 3, 5, 5, 3, 0],
+            // Jumps into the middle of an instruction.
+            &[3, 3, 0, 1, 5, 4, 3, 0],
+        ];
+
+        for &pgm in programs {
+            let actual = assemble(&disassemble(pgm)).unwrap();
+            assert_eq!(pgm, actual);
+        }
+    }
+
+    #[test]
+    fn assemble_run_test() {
+        let src = "
+            ; Prints each octal digit of A, lowest first, xored with 3.
+            loop:
+                bst A
+                bxl 3
+                out B
+                adv 3
+                jnz loop
+        ";
+
+        let pgm = assemble(src).unwrap();
+        assert_eq!(pgm, [2, 4, 1, 3, 5, 5, 0, 3, 3, 0]);
+
+        let machine = Machine {
+            ip: 0,
+            reg: [0o123, 0, 0],
+        };
+        let output = Vm::new(&pgm, machine).run().unwrap();
+        assert_eq!(output, [3 ^ 3, 2 ^ 3, 1 ^ 3]);
+    }
+
+    #[test]
+    fn assemble_error_test() {
+        let cases = [
+            ("adv 7", "line 1: combo operand 7 is reserved"),
+            (
+                "bst A\nbxl 9",
+                "line 2: literal 9 out of range, must be 0-7",
+            ),
+            ("\n\njnz 8", "line 3: literal 8 out of range, must be 0-7"),
+            ("jnz nowhere", "line 1: unknown label `nowhere`"),
+            ("mul A", "line 1: unknown instruction `mul`"),
+            ("out", "line 1: `out` needs an operand"),
+            ("a:\na:", "line 2: duplicate label `a`"),
+        ];
+
+        for (src, expected) in cases {
+            let actual = assemble(src).unwrap_err().to_string();
+            assert_eq!(expected, actual, "{src}");
+        }
+    }
+}
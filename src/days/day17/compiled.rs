@@ -0,0 +1,201 @@
+use super::{Combo, Machine, OpCode, STEP_LIMIT, VmError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Instr {
+    /// `adv`, `bdv` and `cdv` only differ in which register the result goes to.
+    Div {
+        dst: usize,
+        shift: Combo,
+    },
+    Bxl(u64),
+    Bst(Combo),
+    Jnz(usize),
+    Bxc,
+    Out(Combo),
+}
+
+impl Instr {
+    pub(super) fn decode(pgm: &[u8], ip: usize) -> Option<Self> {
+        let opcode = OpCode::from_int(*pgm.get(ip)?)?;
+        let Some(&operand) = pgm.get(ip + 1) else {
+            // `bxc` ignores its operand, so it still runs if the program ends before the operand.
+            return (opcode == OpCode::Bxc).then_some(Instr::Bxc);
+        };
+
+        let instr = match opcode {
+            OpCode::Adv => Instr::Div {
+                dst: 0,
+                shift: Combo::from_int(operand)?,
+            },
+            OpCode::Bdv => Instr::Div {
+                dst: 1,
+                shift: Combo::from_int(operand)?,
+            },
+            OpCode::Cdv => Instr::Div {
+                dst: 2,
+                shift: Combo::from_int(operand)?,
+            },
+            OpCode::Bxl => Instr::Bxl(operand as u64),
+            OpCode::Bst => Instr::Bst(Combo::from_int(operand)?),
+            OpCode::Jnz => Instr::Jnz(operand as usize),
+            OpCode::Bxc => Instr::Bxc,
+            OpCode::Out => Instr::Out(Combo::from_int(operand)?),
+        };
+
+        Some(instr)
+    }
+}
+
+/// A program decoded ahead of time, so running it many times doesn't decode every instruction
+/// on every step.
+///
+/// A jump can land on an odd address, which reads the program with a different alignment, so
+/// there is a decoded instruction for every address. `None` is anything the interpreter would
+/// halt on.
+#[derive(Debug, Clone)]
+pub(super) struct Compiled {
+    instrs: Vec<Option<Instr>>,
+    budget: usize,
+}
+
+impl Compiled {
+    pub(super) fn new(pgm: &[u8]) -> Self {
+        Self {
+            instrs: (0..pgm.len()).map(|ip| Instr::decode(pgm, ip)).collect(),
+            budget: STEP_LIMIT,
+        }
+    }
+
+    /// Runs the program, passing every output value to `on_output`, and stops early if it
+    /// returns `false`.
+    ///
+    /// Unlike the interpreter this doesn't look for repeated states, so a program that never
+    /// halts always runs until it hits the step limit.
+    pub(super) fn run_with(
+        &self,
+        machine: &Machine,
+        mut on_output: impl FnMut(u8) -> bool,
+    ) -> Result<(), VmError> {
+        let mut reg = machine.reg;
+        let mut ip = machine.ip;
+
+        for steps in 0.. {
+            if steps >= self.budget {
+                return Err(VmError::StepLimit { steps });
+            }
+
+            let Some(Some(instr)) = self.instrs.get(ip) else {
+                break;
+            };
+            ip += 2;
+
+            match *instr {
+                Instr::Div { dst, shift } => {
+                    let shift = shift.value(&reg);
+                    reg[dst] = if shift < 64 { reg[0] >> shift } else { 0 };
+                }
+                Instr::Bxl(literal) => reg[1] ^= literal,
+                Instr::Bst(combo) => reg[1] = combo.value(&reg) & 0x7,
+                Instr::Jnz(target) => {
+                    if reg[0] != 0 {
+                        ip = target;
+                    }
+                }
+                Instr::Bxc => reg[1] ^= reg[2],
+                Instr::Out(combo) => {
+                    if !on_output((combo.value(&reg) & 0x7) as u8) {
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn run(&self, machine: &Machine) -> Result<Vec<u8>, VmError> {
+        let mut output = Vec::new();
+        self.run_with(machine, |val| {
+            output.push(val);
+            true
+        })?;
+
+        Ok(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day17::{DAY, Vm, parse};
+    use aoc_lib::Example;
+
+    fn machine(reg: [u64; 3]) -> Machine {
+        Machine { ip: 0, reg }
+    }
+
+    #[test]
+    fn example_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+        let (pgm, machine) = parse(&data).unwrap();
+
+        let expected = Vm::new(&pgm, machine.clone()).run().unwrap();
+        assert_eq!(expected, Compiled::new(&pgm).run(&machine).unwrap());
+    }
+
+    #[test]
+    fn differential_test() {
+        let programs: &[&[u8]] = &[
+            // The part 2 example.
+            &[0, 3, 5, 4, 3, 0],
+            // A typical puzzle input shape.
+            &[2, 4, 1, 1, 7, 5, 1, 5, 4, 0, 0, 3, 5, 5, 3, 0],
+            // Every opcode and combo operand.
+            &[6, 4, 7, 6, 1, 7, 4, 2, 5, 5, 5, 6, 2, 6, 6, 1, 0, 1, 3, 0],
+            // Jumps into the middle of an instruction, reading operands as opcodes.
+            &[3, 3, 0, 1, 5, 4, 3, 0],
+            // An invalid combo operand halts.
+            &[5, 4, 5, 7, 5, 5],
+            // So does jumping past the end.
+            &[0, 1, 5, 4, 3, 9],
+        ];
+
+        for pgm in programs {
+            let compiled = Compiled::new(pgm);
+            for a in (0..500).chain([u64::MAX, 1 << 63]) {
+                for (b, c) in [(0, 0), (5, 3), (1 << 40, u64::MAX)] {
+                    let machine = machine([a, b, c]);
+                    let expected = Vm::new(pgm, machine.clone()).run().unwrap();
+                    let actual = compiled.run(&machine).unwrap();
+                    assert_eq!(expected, actual, "{pgm:?} with {:?}", machine.reg);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn step_limit_test() {
+        let mut compiled = Compiled::new(&[5, 4, 3, 0]);
+        compiled.budget = 5;
+
+        let err = compiled.run(&machine([1, 0, 0])).unwrap_err();
+        assert_eq!(err, VmError::StepLimit { steps: 5 });
+    }
+
+    #[test]
+    fn early_exit_test() {
+        let compiled = Compiled::new(&[0, 1, 5, 4, 3, 0]);
+        let mut output = Vec::new();
+        compiled
+            .run_with(&machine([0b1011, 0, 0]), |val| {
+                output.push(val);
+                output.len() < 2
+            })
+            .unwrap();
+
+        assert_eq!(output, vec![5, 2]);
+    }
+}
@@ -0,0 +1,255 @@
+use std::io::{BufRead, Result, Write};
+
+use super::{Step, Vm, disasm::describe, format_output};
+
+const HELP: &str = "\
+commands:
+    step, s           execute one instruction
+    continue, c       run until a breakpoint, halt, or watched output
+    break <ip>        pause before executing the instruction at <ip>
+    regs              show the instruction pointer and registers
+    set <A|B|C> <n>   set a register
+    watch out         toggle pausing after each output
+    quit, q           stop debugging";
+
+/// A line-oriented debugger. Commands are read from `input` and everything it reports is written
+/// to `out`, so a scripted session gives a predictable transcript.
+pub(super) struct Debugger {
+    vm: Vm,
+    breakpoints: Vec<usize>,
+    watch_output: bool,
+    output: Vec<u8>,
+}
+
+impl Debugger {
+    pub(super) fn new(vm: Vm) -> Self {
+        Self {
+            vm,
+            breakpoints: Vec::new(),
+            watch_output: false,
+            output: Vec::new(),
+        }
+    }
+
+    pub(super) fn output(&self) -> String {
+        format_output(&self.output)
+    }
+
+    fn current_instruction(&self) -> String {
+        let ip = self.vm.machine.ip;
+        match self.vm.program.get(ip) {
+            Some(&opcode) => describe(opcode, self.vm.program.get(ip + 1).copied()),
+            None => "<end of program>".to_owned(),
+        }
+    }
+
+    /// Executes one instruction, reporting any output or halt. Returns whether execution should
+    /// pause.
+    fn step(&mut self, out: &mut impl Write) -> Result<bool> {
+        let ip = self.vm.machine.ip;
+
+        match self.vm.step() {
+            Err(e) => {
+                writeln!(out, "error: {e}")?;
+                Ok(true)
+            }
+            Ok(Step::Continue) => Ok(false),
+            Ok(Step::Output(val)) => {
+                self.output.push(val);
+                writeln!(out, "out {val}")?;
+                Ok(self.watch_output)
+            }
+            Ok(Step::Halted) => {
+                writeln!(out, "halted at ip {ip}")?;
+                Ok(true)
+            }
+        }
+    }
+
+    fn exec(&mut self, command: &[&str], out: &mut impl Write) -> Result<()> {
+        match command {
+            [] => {}
+            ["step" | "s"] if self.vm.halted => writeln!(out, "program has halted")?,
+            ["step" | "s"] => {
+                writeln!(
+                    out,
+                    "{}: {}",
+                    self.vm.machine.ip,
+                    self.current_instruction()
+                )?;
+                self.step(out)?;
+            }
+            ["continue" | "c"] if self.vm.halted => writeln!(out, "program has halted")?,
+            ["continue" | "c"] => loop {
+                if self.step(out)? {
+                    if self.watch_output && !self.vm.halted {
+                        writeln!(out, "paused after output")?;
+                    }
+                    break;
+                }
+
+                let ip = self.vm.machine.ip;
+                if self.breakpoints.contains(&ip) {
+                    writeln!(out, "breakpoint at ip {ip}")?;
+                    break;
+                }
+            },
+            ["break", ip] => match ip.parse() {
+                Ok(ip) => {
+                    if !self.breakpoints.contains(&ip) {
+                        self.breakpoints.push(ip);
+                    }
+                    writeln!(out, "breakpoint set at ip {ip}")?;
+                }
+                Err(_) => writeln!(out, "invalid instruction pointer `{ip}`")?,
+            },
+            ["regs"] => {
+                let [a, b, c] = self.vm.machine.reg;
+                writeln!(out, "ip={} A={a} B={b} C={c}", self.vm.machine.ip)?;
+            }
+            ["set", reg, value] => {
+                let idx = match *reg {
+                    "A" => 0,
+                    "B" => 1,
+                    "C" => 2,
+                    _ => return writeln!(out, "unknown register `{reg}`"),
+                };
+
+                match value.parse() {
+                    Ok(value) => {
+                        self.vm.machine.reg[idx] = value;
+                        // The states seen so far were reached without this change, so they can't
+                        // show that the program loops from here.
+                        self.vm.jump_states.clear();
+                        self.vm.steps = 0;
+                        writeln!(out, "{reg}={value}")?;
+                    }
+                    Err(_) => writeln!(out, "invalid value `{value}`")?,
+                }
+            }
+            ["watch", "out"] => {
+                self.watch_output = !self.watch_output;
+                if self.watch_output {
+                    writeln!(out, "watching output")?;
+                } else {
+                    writeln!(out, "no longer watching output")?;
+                }
+            }
+            ["help"] => writeln!(out, "{HELP}")?,
+            _ => writeln!(out, "unknown command `{}`, try `help`", command.join(" "))?,
+        }
+
+        Ok(())
+    }
+
+    pub(super) fn run(&mut self, input: impl BufRead, mut out: impl Write) -> Result<()> {
+        for line in input.lines() {
+            let line = line?;
+            let command: Vec<_> = line.split_ascii_whitespace().collect();
+            if let ["quit" | "q"] = *command {
+                break;
+            }
+
+            self.exec(&command, &mut out)?;
+            out.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day17::Machine;
+
+    fn session(commands: &str) -> (String, String) {
+        let machine = Machine {
+            ip: 0,
+            reg: [729, 0, 0],
+        };
+        let mut debugger = Debugger::new(Vm::new(&[0, 1, 5, 4, 3, 0], machine));
+
+        let mut transcript = Vec::new();
+        debugger.run(commands.as_bytes(), &mut transcript).unwrap();
+
+        (String::from_utf8(transcript).unwrap(), debugger.output())
+    }
+
+    #[test]
+    fn step_test() {
+        let (transcript, output) = session("regs\nstep\nstep\nregs\nstep\nregs\n");
+        let expected = "\
+ip=0 A=729 B=0 C=0
+0: adv 1
+2: out A
+out 4
+ip=4 A=364 B=0 C=0
+4: jnz 0
+ip=0 A=364 B=0 C=0
+";
+
+        assert_eq!(expected, transcript);
+        assert_eq!("4", output);
+    }
+
+    #[test]
+    fn break_watch_test() {
+        let commands = "\
+break 4
+continue
+regs
+watch out
+continue
+watch out
+set A 3
+c
+c
+step
+c
+step
+bogus
+q
+step
+";
+        let (transcript, output) = session(commands);
+        let expected = "\
+breakpoint set at ip 4
+out 4
+breakpoint at ip 4
+ip=4 A=364 B=0 C=0
+watching output
+out 6
+paused after output
+no longer watching output
+A=3
+out 1
+breakpoint at ip 4
+out 0
+breakpoint at ip 4
+4: jnz 0
+halted at ip 6
+program has halted
+unknown command `bogus`, try `help`
+";
+
+        assert_eq!(expected, transcript);
+        assert_eq!("4,6,1,0", output);
+
+        // Setting a register back to an earlier value isn't a loop.
+        let (transcript, output) = session("break 4\nc\nc\nset A 364\nc\n");
+        let expected = "\
+breakpoint set at ip 4
+out 4
+breakpoint at ip 4
+out 6
+breakpoint at ip 4
+A=364
+out 6
+breakpoint at ip 4
+";
+
+        assert_eq!(expected, transcript);
+        assert_eq!("4,6,6", output);
+    }
+}
@@ -0,0 +1,103 @@
+use std::fmt::Write;
+
+use super::{Combo, OpCode};
+
+/// Renders a single instruction. Jump targets are shown as plain addresses.
+pub(super) fn describe(opcode: u8, operand: Option<u8>) -> String {
+    let Some(operand) = operand else {
+        return format!("; trailing opcode with no operand: {opcode}");
+    };
+
+    let Some(opcode) = OpCode::from_int(opcode) else {
+        return format!("; invalid opcode: {opcode},{operand}");
+    };
+
+    match opcode {
+        OpCode::Adv | OpCode::Bst | OpCode::Out | OpCode::Bdv | OpCode::Cdv => {
+            match Combo::from_int(operand) {
+                Some(combo) => format!("{} {combo}", opcode.mnemonic()),
+                None => format!("; invalid combo operand: {} {operand}", opcode.mnemonic()),
+            }
+        }
+        OpCode::Bxc if operand == 0 => "bxc".to_owned(),
+        OpCode::Bxl | OpCode::Jnz | OpCode::Bxc => format!("{} {operand}", opcode.mnemonic()),
+    }
+}
+
+/// Renders a program as one mnemonic per line, with combo operands shown as register names or
+/// immediates. Any instruction-aligned jump target gets a label.
+pub(super) fn disassemble(pgm: &[u8]) -> String {
+    let instrs = pgm.chunks_exact(2);
+    let trailing = instrs.remainder();
+
+    let is_label = |target: u8| {
+        let target = target as usize;
+        target.is_multiple_of(2)
+            && target < pgm.len() - trailing.len()
+            && instrs
+                .clone()
+                .any(|i| OpCode::from_int(i[0]) == Some(OpCode::Jnz) && i[1] as usize == target)
+    };
+
+    let mut output = String::new();
+
+    for (ip, instr) in (0..).step_by(2).zip(instrs.clone()) {
+        let &[opcode, operand] = instr else {
+            unreachable!()
+        };
+
+        if is_label(ip) {
+            let _ = writeln!(output, "L{ip}:");
+        }
+
+        if OpCode::from_int(opcode) == Some(OpCode::Jnz) && is_label(operand) {
+            let _ = writeln!(output, "    jnz L{operand}");
+        } else {
+            let _ = writeln!(output, "    {}", describe(opcode, Some(operand)));
+        }
+    }
+
+    if let &[opcode] = trailing {
+        let _ = writeln!(output, "    {}", describe(opcode, None));
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disassemble_test() {
+        let pgm = [2, 4, 1, 5, 7, 5, 4, 3, 1, 6, 0, 3, 5, 5, 3, 0];
+        let expected = "\
+L0:
+    bst A
+    bxl 5
+    cdv B
+    bxc 3
+    bxl 6
+    adv 3
+    out B
+    jnz L0
+";
+        let actual = disassemble(&pgm);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn disassemble_invalid_test() {
+        let pgm = [0, 7, 3, 5, 4, 0, 9, 1, 5];
+        let expected = "    ; invalid combo operand: adv 7
+    jnz 5
+    bxc
+    ; invalid opcode: 9,1
+    ; trailing opcode with no operand: 5
+";
+        let actual = disassemble(&pgm);
+
+        assert_eq!(expected, actual);
+    }
+}
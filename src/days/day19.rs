@@ -0,0 +1,181 @@
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 19,
+    name: "Linen Layout",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part::<true>(&data)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part::<false>(&data)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+fn colour_index(c: u8) -> Result<u8> {
+    match c {
+        b'w' => Ok(0),
+        b'u' => Ok(1),
+        b'b' => Ok(2),
+        b'r' => Ok(3),
+        b'g' => Ok(4),
+        _ => Err(eyre!("Invalid colour: {}", c as char)),
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct TrieNode {
+    // 0 means no child, as the root can never be a child.
+    children: [u32; 5],
+    is_towel: bool,
+}
+
+#[derive(Debug)]
+struct Trie {
+    nodes: Vec<TrieNode>,
+}
+
+impl Trie {
+    fn new() -> Self {
+        Self {
+            nodes: vec![TrieNode::default()],
+        }
+    }
+
+    fn insert(&mut self, towel: &[u8]) {
+        let mut cur = 0;
+
+        for &c in towel {
+            let next = self.nodes[cur].children[c as usize];
+            cur = if next == 0 {
+                let next = self.nodes.len();
+                self.nodes.push(TrieNode::default());
+                self.nodes[cur].children[c as usize] = next as u32;
+                next
+            } else {
+                next as usize
+            };
+        }
+
+        self.nodes[cur].is_towel = true;
+    }
+
+    /// Calls `f` with the length of every towel that is a prefix of `design`.
+    fn prefixes(&self, design: &[u8], mut f: impl FnMut(usize)) {
+        let mut cur = 0;
+
+        for (i, &c) in design.iter().enumerate() {
+            let next = self.nodes[cur].children[c as usize];
+            if next == 0 {
+                return;
+            }
+
+            cur = next as usize;
+            if self.nodes[cur].is_towel {
+                f(i + 1);
+            }
+        }
+    }
+}
+
+fn parse(input: &str) -> Result<(Trie, Vec<Vec<u8>>)> {
+    let (towels, designs) = input
+        .trim()
+        .split_once("\n\n")
+        .ok_or_else(|| eyre!("Invalid input"))?;
+
+    let mut trie = Trie::new();
+    for towel in towels.split(',') {
+        let towel = towel
+            .trim()
+            .bytes()
+            .map(colour_index)
+            .collect::<Result<Vec<_>>>()?;
+        trie.insert(&towel);
+    }
+
+    let designs = designs
+        .lines()
+        .map(|d| d.trim().bytes().map(colour_index).collect())
+        .collect::<Result<_>>()?;
+
+    Ok((trie, designs))
+}
+
+fn part<const P1: bool>((trie, designs): &(Trie, Vec<Vec<u8>>)) -> u64 {
+    let mut ways = Vec::new();
+    let mut total = 0;
+
+    for design in designs {
+        // ways[i] is the number of arrangements of design[..i].
+        ways.clear();
+        ways.resize(design.len() + 1, 0u64);
+        ways[0] = 1;
+
+        for start in 0..design.len() {
+            let cur = ways[start];
+            if cur == 0 {
+                continue;
+            }
+
+            trie.prefixes(&design[start..], |len| ways[start + len] += cur);
+        }
+
+        let design_ways = ways[design.len()];
+        if P1 {
+            total += (design_ways > 0) as u64;
+        } else {
+            total += design_ways;
+        }
+    }
+
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn part1_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 6;
+        let actual = part::<true>(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 16;
+        let actual = part::<false>(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,186 @@
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 20,
+    name: "Race Condition",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part(&data, 2, 100)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part(&data, 20, 100)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Point {
+    x: u8,
+    y: u8,
+}
+
+impl Point {
+    fn neighbours(self) -> [Self; 4] {
+        [
+            Point {
+                x: self.x,
+                y: self.y.wrapping_sub(1),
+            },
+            Point {
+                x: self.x,
+                y: self.y.wrapping_add(1),
+            },
+            Point {
+                x: self.x.wrapping_sub(1),
+                y: self.y,
+            },
+            Point {
+                x: self.x.wrapping_add(1),
+                y: self.y,
+            },
+        ]
+    }
+
+    fn manhattan(self, other: Self) -> usize {
+        self.x.abs_diff(other.x) as usize + self.y.abs_diff(other.y) as usize
+    }
+}
+
+/// Returns the track in order from start to end, so a tile's index is its distance from the start.
+fn parse(input: &str) -> Result<Vec<Point>> {
+    let mut walls = Vec::new();
+    let mut start = None;
+    let mut end = None;
+    let mut height = 0;
+
+    for (y, row) in input.trim().lines().enumerate() {
+        for (x, tile) in row.trim().bytes().enumerate() {
+            let p = Point {
+                x: x as u8,
+                y: y as u8,
+            };
+
+            match tile {
+                b'S' => start = Some(p),
+                b'E' => end = Some(p),
+                _ => {}
+            }
+
+            walls.push(tile == b'#');
+        }
+
+        height += 1;
+    }
+
+    let width = walls.len() / height;
+    let start = start.ok_or_else(|| eyre!("No start tile"))?;
+    let end = end.ok_or_else(|| eyre!("No end tile"))?;
+
+    let is_track = |p: Point| {
+        (p.x as usize) < width
+            && (p.y as usize) < height
+            && !walls[p.y as usize * width + p.x as usize]
+    };
+
+    let mut track = vec![start];
+    let mut prev = start;
+    let mut cur = start;
+
+    while cur != end {
+        let mut next = cur
+            .neighbours()
+            .into_iter()
+            .filter(|&n| n != prev && is_track(n));
+
+        let Some(step) = next.next() else {
+            return Err(eyre!("Track dead-ends at {},{}", cur.x, cur.y));
+        };
+        if next.next().is_some() {
+            return Err(eyre!("Track branches at {},{}", cur.x, cur.y));
+        }
+
+        prev = cur;
+        cur = step;
+        track.push(cur);
+    }
+
+    Ok(track)
+}
+
+fn part(track: &[Point], max_cheat: usize, min_saving: usize) -> usize {
+    let mut count = 0;
+
+    for (i, &from) in track.iter().enumerate() {
+        // A cheat covers at least one step, so only tiles more than `min_saving` further along the
+        // track can possibly save enough.
+        for (j, &to) in track.iter().enumerate().skip(i + min_saving + 1) {
+            let cheat_len = from.manhattan(to);
+            if cheat_len <= max_cheat && (j - i) - cheat_len >= min_saving {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn part1_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+
+        for (min_saving, expected) in [
+            (64, 1),
+            (40, 2),
+            (38, 3),
+            (36, 4),
+            (20, 5),
+            (12, 8),
+            (10, 10),
+            (8, 14),
+            (6, 16),
+            (4, 30),
+            (2, 44),
+        ] {
+            let actual = part(&parsed, 2, min_saving);
+            assert_eq!(expected, actual, "{min_saving}");
+        }
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+
+        for (min_saving, expected) in [(76, 3), (74, 7), (72, 29), (70, 41), (50, 285)] {
+            let actual = part(&parsed, 20, min_saving);
+            assert_eq!(expected, actual, "{min_saving}");
+        }
+    }
+}
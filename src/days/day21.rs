@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 21,
+    name: "Keypad Conundrum",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part(&data, 2)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part(&data, 25)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Pos {
+    row: i8,
+    col: i8,
+}
+
+// +---+---+---+
+// | 7 | 8 | 9 |
+// +---+---+---+
+// | 4 | 5 | 6 |
+// +---+---+---+
+// | 1 | 2 | 3 |
+// +---+---+---+
+//     | 0 | A |
+//     +---+---+
+const NUMERIC_GAP: Pos = Pos { row: 3, col: 0 };
+
+fn numeric_pos(key: u8) -> Pos {
+    let (row, col) = match key {
+        b'7' => (0, 0),
+        b'8' => (0, 1),
+        b'9' => (0, 2),
+        b'4' => (1, 0),
+        b'5' => (1, 1),
+        b'6' => (1, 2),
+        b'1' => (2, 0),
+        b'2' => (2, 1),
+        b'3' => (2, 2),
+        b'0' => (3, 1),
+        b'A' => (3, 2),
+        _ => unreachable!("validated during parsing"),
+    };
+
+    Pos { row, col }
+}
+
+//     +---+---+
+//     | ^ | A |
+// +---+---+---+
+// | < | v | > |
+// +---+---+---+
+const DIRECTIONAL_GAP: Pos = Pos { row: 0, col: 0 };
+
+fn directional_pos(key: u8) -> Pos {
+    let (row, col) = match key {
+        b'^' => (0, 1),
+        b'A' => (0, 2),
+        b'<' => (1, 0),
+        b'v' => (1, 1),
+        b'>' => (1, 2),
+        _ => unreachable!(),
+    };
+
+    Pos { row, col }
+}
+
+/// Returns the candidate key sequences, ending in `A`, for moving from `from` to `to` and pressing
+/// the button.
+///
+/// Zig-zagging is never better than doing all of one axis then all of the other, because repeated
+/// presses of the same direction are free for the robot above. That leaves at most two options,
+/// minus any that would pass over the gap.
+fn moves(from: Pos, to: Pos, gap: Pos) -> impl Iterator<Item = Vec<u8>> {
+    let d_row = to.row - from.row;
+    let d_col = to.col - from.col;
+
+    let vert = if d_row < 0 { b'^' } else { b'v' };
+    let horiz = if d_col < 0 { b'<' } else { b'>' };
+    let verts = std::iter::repeat_n(vert, d_row.unsigned_abs() as usize);
+    let horizs = std::iter::repeat_n(horiz, d_col.unsigned_abs() as usize);
+
+    let horiz_first_ok = !(from.row == gap.row && to.col == gap.col);
+    let vert_first_ok = !(from.col == gap.col && to.row == gap.row);
+
+    let horiz_first = horiz_first_ok.then(|| {
+        horizs
+            .clone()
+            .chain(verts.clone())
+            .chain([b'A'])
+            .collect::<Vec<_>>()
+    });
+    let vert_first = (vert_first_ok && d_row != 0 && d_col != 0)
+        .then(|| verts.chain(horizs).chain([b'A']).collect::<Vec<_>>());
+
+    horiz_first.into_iter().chain(vert_first)
+}
+
+struct Solver {
+    cache: HashMap<(u8, u8, u8), u64>,
+}
+
+impl Solver {
+    /// The number of presses needed on the outermost keypad to type `seq` on a directional
+    /// keypad with `depth` robot-operated directional keypads in between.
+    fn sequence_cost(&mut self, seq: &[u8], depth: u8) -> u64 {
+        if depth == 0 {
+            return seq.len() as u64;
+        }
+
+        let mut prev = b'A';
+        let mut total = 0;
+        for &key in seq {
+            total += self.directional_cost(prev, key, depth);
+            prev = key;
+        }
+
+        total
+    }
+
+    fn directional_cost(&mut self, from: u8, to: u8, depth: u8) -> u64 {
+        if let Some(&cost) = self.cache.get(&(from, to, depth)) {
+            return cost;
+        }
+
+        let cost = moves(directional_pos(from), directional_pos(to), DIRECTIONAL_GAP)
+            .map(|seq| self.sequence_cost(&seq, depth - 1))
+            .min()
+            .unwrap_or(u64::MAX);
+
+        self.cache.insert((from, to, depth), cost);
+        cost
+    }
+
+    fn code_cost(&mut self, code: &[u8], robots: u8) -> u64 {
+        let mut prev = b'A';
+        let mut total = 0;
+
+        for &key in code {
+            total += moves(numeric_pos(prev), numeric_pos(key), NUMERIC_GAP)
+                .map(|seq| self.sequence_cost(&seq, robots))
+                .min()
+                .unwrap_or(u64::MAX);
+            prev = key;
+        }
+
+        total
+    }
+}
+
+fn parse(input: &str) -> Result<Vec<Vec<u8>>> {
+    let mut codes = Vec::new();
+
+    for line in input.trim().lines() {
+        let code = line.trim().as_bytes();
+        if !code.iter().all(|c| matches!(c, b'0'..=b'9' | b'A')) {
+            return Err(eyre!("Invalid code: {line}"));
+        }
+
+        codes.push(code.to_vec());
+    }
+
+    Ok(codes)
+}
+
+/// `robots` is the number of robot-operated directional keypads between us and the numeric
+/// keypad.
+fn part(codes: &[Vec<u8>], robots: u8) -> u64 {
+    let mut solver = Solver {
+        cache: HashMap::new(),
+    };
+
+    codes
+        .iter()
+        .map(|code| {
+            let numeric = code
+                .iter()
+                .filter(|c| c.is_ascii_digit())
+                .fold(0, |acc, c| acc * 10 + (c - b'0') as u64);
+
+            solver.code_cost(code, robots) * numeric
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn depth_test() {
+        let mut solver = Solver {
+            cache: HashMap::new(),
+        };
+
+        for (robots, expected) in [(0, 12), (1, 28), (2, 68)] {
+            let actual = solver.code_cost(b"029A", robots);
+            assert_eq!(expected, actual, "{robots}");
+        }
+    }
+
+    #[test]
+    fn part1_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 126384;
+        let actual = part(&parsed, 2);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 154115708116294;
+        let actual = part(&parsed, 25);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,129 @@
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result};
+
+pub const DAY: Day = Day {
+    day: 22,
+    name: "Monkey Market",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part1(&data)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part2(&data)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+const PRUNE_MASK: u32 = 16777216 - 1;
+
+fn next_secret(mut secret: u32) -> u32 {
+    secret = ((secret << 6) ^ secret) & PRUNE_MASK;
+    secret = ((secret >> 5) ^ secret) & PRUNE_MASK;
+    ((secret << 11) ^ secret) & PRUNE_MASK
+}
+
+fn parse(input: &str) -> Result<Vec<u32>> {
+    input
+        .trim()
+        .lines()
+        .map(|l| Ok(l.trim().parse()?))
+        .collect()
+}
+
+fn part1(buyers: &[u32]) -> u64 {
+    buyers
+        .iter()
+        .map(|&b| (0..2000).fold(b, |s, _| next_secret(s)) as u64)
+        .sum()
+}
+
+// Each change is in -9..=9, so four of them fit in base 19.
+const SEQUENCE_COUNT: usize = 19 * 19 * 19 * 19;
+
+fn part2(buyers: &[u32]) -> u32 {
+    let mut totals = vec![0u32; SEQUENCE_COUNT];
+    // The last buyer (plus one) to see each sequence, so only the first occurrence is counted
+    // without needing to clear the array between buyers.
+    let mut last_seen = vec![0u32; SEQUENCE_COUNT];
+
+    for (buyer_id, &secret) in (1..).zip(buyers) {
+        let mut secret = secret;
+        let mut price = secret % 10;
+        let mut sequence = 0;
+
+        for i in 0..2000 {
+            secret = next_secret(secret);
+            let next_price = secret % 10;
+            let change = (next_price + 9 - price) as usize;
+            price = next_price;
+
+            sequence = (sequence * 19 + change) % SEQUENCE_COUNT;
+            if i >= 3 && last_seen[sequence] != buyer_id {
+                last_seen[sequence] = buyer_id;
+                totals[sequence] += price;
+            }
+        }
+    }
+
+    totals.into_iter().max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn secret_test() {
+        let expected = [
+            15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+            5908254,
+        ];
+
+        let mut secret = 123;
+        for expected in expected {
+            secret = next_secret(secret);
+            assert_eq!(expected, secret);
+        }
+    }
+
+    #[test]
+    fn part1_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 37327623;
+        let actual = part1(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part2, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 23;
+        let actual = part2(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+}
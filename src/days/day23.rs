@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 23,
+    name: "LAN Party",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part1(&data)))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part2(&data)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+// Names are two lowercase letters, so there can be at most 26 * 26 computers.
+const MAX_COMPUTERS: usize = 26 * 26;
+const WORDS: usize = MAX_COMPUTERS.div_ceil(64);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BitSet([u64; WORDS]);
+
+impl BitSet {
+    const EMPTY: Self = BitSet([0; WORDS]);
+
+    fn insert(&mut self, i: usize) {
+        self.0[i / 64] |= 1 << (i % 64);
+    }
+
+    fn remove(&mut self, i: usize) {
+        self.0[i / 64] &= !(1 << (i % 64));
+    }
+
+    fn and(self, rhs: Self) -> Self {
+        let mut out = self;
+        out.0.iter_mut().zip(rhs.0).for_each(|(a, b)| *a &= b);
+        out
+    }
+
+    fn and_not(self, rhs: Self) -> Self {
+        let mut out = self;
+        out.0.iter_mut().zip(rhs.0).for_each(|(a, b)| *a &= !b);
+        out
+    }
+
+    fn or(self, rhs: Self) -> Self {
+        let mut out = self;
+        out.0.iter_mut().zip(rhs.0).for_each(|(a, b)| *a |= b);
+        out
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|w| *w == 0)
+    }
+
+    fn len(&self) -> u32 {
+        self.0.iter().map(|w| w.count_ones()).sum()
+    }
+
+    fn iter(self) -> impl Iterator<Item = usize> {
+        self.0.into_iter().enumerate().flat_map(|(wi, mut word)| {
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(wi * 64 + bit)
+            })
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Network {
+    names: Vec<[u8; 2]>,
+    adjacency: Vec<BitSet>,
+}
+
+fn parse(input: &str) -> Result<Network> {
+    let mut ids: HashMap<[u8; 2], usize> = HashMap::new();
+    let mut network = Network {
+        names: Vec::new(),
+        adjacency: Vec::new(),
+    };
+
+    let mut intern = |name: &str, network: &mut Network| -> Result<usize> {
+        let &[a @ b'a'..=b'z', b @ b'a'..=b'z'] = name.as_bytes() else {
+            return Err(eyre!("Invalid computer name: {name}"));
+        };
+
+        Ok(*ids.entry([a, b]).or_insert_with(|| {
+            network.names.push([a, b]);
+            network.adjacency.push(BitSet::EMPTY);
+            network.names.len() - 1
+        }))
+    };
+
+    for line in input.trim().lines() {
+        let (a, b) = line
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| eyre!("Invalid connection: {line}"))?;
+
+        let a = intern(a, &mut network)?;
+        let b = intern(b, &mut network)?;
+        network.adjacency[a].insert(b);
+        network.adjacency[b].insert(a);
+    }
+
+    Ok(network)
+}
+
+fn part1(network: &Network) -> usize {
+    let is_t = |i: usize| network.names[i][0] == b't';
+    let mut count = 0;
+
+    for (a, a_adj) in network.adjacency.iter().enumerate() {
+        // Only visit each triangle once, with a < b < c.
+        for b in a_adj.iter().filter(|&b| b > a) {
+            let common = a_adj.and(network.adjacency[b]);
+            for c in common.iter().filter(|&c| c > b) {
+                count += (is_t(a) || is_t(b) || is_t(c)) as usize;
+            }
+        }
+    }
+
+    count
+}
+
+fn bron_kerbosch(
+    adjacency: &[BitSet],
+    clique: BitSet,
+    mut candidates: BitSet,
+    mut excluded: BitSet,
+    best: &mut BitSet,
+) {
+    if candidates.is_empty() {
+        if excluded.is_empty() && clique.len() > best.len() {
+            *best = clique;
+        }
+        return;
+    }
+
+    // Pivot on the vertex with the most candidate neighbours to cut down on branching.
+    let Some(pivot) = candidates
+        .or(excluded)
+        .iter()
+        .max_by_key(|&v| adjacency[v].and(candidates).len())
+    else {
+        return;
+    };
+
+    for v in candidates.and_not(adjacency[pivot]).iter() {
+        let mut next_clique = clique;
+        next_clique.insert(v);
+
+        bron_kerbosch(
+            adjacency,
+            next_clique,
+            candidates.and(adjacency[v]),
+            excluded.and(adjacency[v]),
+            best,
+        );
+
+        candidates.remove(v);
+        excluded.insert(v);
+    }
+}
+
+fn part2(network: &Network) -> String {
+    let mut all = BitSet::EMPTY;
+    for i in 0..network.names.len() {
+        all.insert(i);
+    }
+
+    let mut best = BitSet::EMPTY;
+    bron_kerbosch(
+        &network.adjacency,
+        BitSet::EMPTY,
+        all,
+        BitSet::EMPTY,
+        &mut best,
+    );
+
+    let mut names: Vec<_> = best.iter().map(|i| network.names[i]).collect();
+    names.sort_unstable();
+
+    let mut password = String::new();
+    for [a, b] in names {
+        password.push(a as char);
+        password.push(b as char);
+        password.push(',');
+    }
+
+    password.pop(); // remove trailing comma
+    password
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn bitset_test() {
+        let mut set = BitSet::EMPTY;
+        set.insert(3);
+        set.insert(64);
+        set.insert(675);
+
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3, 64, 675]);
+
+        set.remove(64);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [3, 675]);
+    }
+
+    #[test]
+    fn part1_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 7;
+        let actual = part1(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part2_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = "co,de,ka,ta";
+        let actual = part2(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,357 @@
+use std::collections::HashMap;
+
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 24,
+    name: "Crossed Wires",
+    part_1: run_part1,
+    part_2: Some(run_part2),
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| part1(&data))
+}
+
+fn run_part2(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part2(&data)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+impl Op {
+    fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Op::And => a & b,
+            Op::Or => a | b,
+            Op::Xor => a ^ b,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Gate {
+    lhs: usize,
+    rhs: usize,
+    op: Op,
+    out: usize,
+}
+
+#[derive(Debug)]
+struct Circuit {
+    names: Vec<[u8; 3]>,
+    initial: Vec<Option<bool>>,
+    gates: Vec<Gate>,
+}
+
+impl Circuit {
+    fn is_input(&self, wire: usize) -> bool {
+        matches!(self.names[wire][0], b'x' | b'y')
+    }
+
+    fn is_output(&self, wire: usize) -> bool {
+        self.names[wire][0] == b'z'
+    }
+
+    fn is_first_bit(&self, wire: usize) -> bool {
+        self.names[wire][1..] == *b"00"
+    }
+}
+
+fn parse(input: &str) -> Result<Circuit> {
+    let (initial_str, gates_str) = input
+        .trim()
+        .split_once("\n\n")
+        .ok_or_else(|| eyre!("Invalid input"))?;
+
+    let mut ids: HashMap<[u8; 3], usize> = HashMap::new();
+    let mut names = Vec::new();
+    let mut intern = |name: &str| -> Result<usize> {
+        let name: [u8; 3] = name
+            .as_bytes()
+            .try_into()
+            .map_err(|_| eyre!("Invalid wire name: {name}"))?;
+
+        Ok(*ids.entry(name).or_insert_with(|| {
+            names.push(name);
+            names.len() - 1
+        }))
+    };
+
+    let mut initial_values = Vec::new();
+    for line in initial_str.lines() {
+        let (name, value) = line
+            .split_once(": ")
+            .ok_or_else(|| eyre!("Invalid wire value: {line}"))?;
+
+        let value = match value.trim() {
+            "0" => false,
+            "1" => true,
+            _ => return Err(eyre!("Invalid wire value: {line}")),
+        };
+
+        initial_values.push((intern(name.trim())?, value));
+    }
+
+    let mut gates = Vec::new();
+    for line in gates_str.lines() {
+        let [lhs, op, rhs, "->", out] = *line.split_ascii_whitespace().collect::<Vec<_>>() else {
+            return Err(eyre!("Invalid gate: {line}"));
+        };
+
+        let op = match op {
+            "AND" => Op::And,
+            "OR" => Op::Or,
+            "XOR" => Op::Xor,
+            _ => return Err(eyre!("Invalid gate operation: {op}")),
+        };
+
+        gates.push(Gate {
+            lhs: intern(lhs)?,
+            rhs: intern(rhs)?,
+            op,
+            out: intern(out)?,
+        });
+    }
+
+    let mut initial = vec![None; names.len()];
+    for (wire, value) in initial_values {
+        initial[wire] = Some(value);
+    }
+
+    Ok(Circuit {
+        names,
+        initial,
+        gates,
+    })
+}
+
+fn part1(circuit: &Circuit) -> Result<u64> {
+    let mut values = circuit.initial.clone();
+    let mut pending: Vec<_> = circuit.gates.clone();
+
+    while !pending.is_empty() {
+        let before = pending.len();
+
+        pending.retain(|g| match (values[g.lhs], values[g.rhs]) {
+            (Some(lhs), Some(rhs)) => {
+                values[g.out] = Some(g.op.apply(lhs, rhs));
+                false
+            }
+            _ => true,
+        });
+
+        if pending.len() == before {
+            return Err(eyre!("Circuit contains a loop or an undriven wire"));
+        }
+    }
+
+    let mut z_wires: Vec<_> = (0..circuit.names.len())
+        .filter(|&w| circuit.is_output(w))
+        .collect();
+    z_wires.sort_unstable_by_key(|&w| circuit.names[w]);
+
+    let mut output = 0;
+    for &wire in z_wires.iter().rev() {
+        let value = values[wire].ok_or_else(|| eyre!("Output wire was never set"))?;
+        output = (output << 1) | value as u64;
+    }
+
+    Ok(output)
+}
+
+fn part2(circuit: &Circuit) -> String {
+    // The circuit should be a ripple-carry adder, where each bit after the first is a full adder:
+    //
+    //   x XOR y -> s        s XOR carry_in -> z
+    //   x AND y -> a        s AND carry_in -> t
+    //                       a OR t -> carry_out
+    //
+    // Rather than trying swaps, check every gate's output against where it should go in that
+    // shape. Any gate that doesn't fit has had its output swapped.
+    let last_z = circuit
+        .names
+        .iter()
+        .filter(|n| n[0] == b'z')
+        .max()
+        .copied()
+        .unwrap_or(*b"z00");
+
+    let feeds = |wire: usize, op: Op| {
+        circuit
+            .gates
+            .iter()
+            .any(|g| g.op == op && (g.lhs == wire || g.rhs == wire))
+    };
+
+    let mut wrong = Vec::new();
+
+    for gate in &circuit.gates {
+        let from_inputs = circuit.is_input(gate.lhs) && circuit.is_input(gate.rhs);
+        let first_bit = from_inputs && circuit.is_first_bit(gate.lhs);
+
+        let is_wrong = if circuit.is_output(gate.out) {
+            // Every output bit is the sum from an XOR, except the top bit which is the final
+            // carry.
+            if circuit.names[gate.out] == last_z {
+                gate.op != Op::Or
+            } else {
+                gate.op != Op::Xor
+            }
+        } else {
+            match gate.op {
+                // The second XOR of a full adder always produces an output bit.
+                Op::Xor if !from_inputs => true,
+                // The first XOR's result goes on to the second XOR.
+                Op::Xor => !feeds(gate.out, Op::Xor),
+                // Both ANDs go into the carry OR, except on the first bit where there is no
+                // carry in, and the AND is the carry.
+                Op::And if first_bit => false,
+                Op::And => !feeds(gate.out, Op::Or),
+                // The carry goes into the next bit's XOR and AND.
+                Op::Or => !feeds(gate.out, Op::Xor) || !feeds(gate.out, Op::And),
+            }
+        };
+
+        if is_wrong {
+            wrong.push(circuit.names[gate.out]);
+        }
+    }
+
+    wrong.sort_unstable();
+    wrong
+        .iter()
+        .map(|n| std::str::from_utf8(n).unwrap_or("???"))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn part1_test1() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 4;
+        let actual = part1(&parsed).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn part1_test2() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 2)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 2024;
+        let actual = part1(&parsed).unwrap();
+
+        assert_eq!(expected, actual);
+    }
+
+    /// Builds a ripple-carry adder for `bits`-bit numbers, with the given gate outputs swapped.
+    fn adder(bits: u32, x: u64, y: u64, swaps: &[(&str, &str)]) -> String {
+        let mut input = String::new();
+        for i in 0..bits {
+            input += &format!("x{i:02}: {}\n", (x >> i) & 1);
+        }
+        for i in 0..bits {
+            input += &format!("y{i:02}: {}\n", (y >> i) & 1);
+        }
+        input += "\n";
+
+        let swap = |name: String| {
+            for &(a, b) in swaps {
+                if name == a {
+                    return b.to_owned();
+                } else if name == b {
+                    return a.to_owned();
+                }
+            }
+
+            name
+        };
+
+        let mut gate = |lhs: String, op: &str, rhs: String, out: String| {
+            input += &format!("{lhs} {op} {rhs} -> {}\n", swap(out));
+        };
+
+        gate("x00".into(), "XOR", "y00".into(), "z00".into());
+        gate("x00".into(), "AND", "y00".into(), "c00".into());
+        for i in 1..bits {
+            let carry_in = format!("c{:02}", i - 1);
+            let carry_out = if i == bits - 1 {
+                format!("z{bits:02}")
+            } else {
+                format!("c{i:02}")
+            };
+
+            gate(
+                format!("x{i:02}"),
+                "XOR",
+                format!("y{i:02}"),
+                format!("s{i:02}"),
+            );
+            gate(
+                format!("x{i:02}"),
+                "AND",
+                format!("y{i:02}"),
+                format!("a{i:02}"),
+            );
+            gate(
+                format!("s{i:02}"),
+                "XOR",
+                carry_in.clone(),
+                format!("z{i:02}"),
+            );
+            gate(format!("s{i:02}"), "AND", carry_in, format!("t{i:02}"));
+            gate(format!("a{i:02}"), "OR", format!("t{i:02}"), carry_out);
+        }
+
+        input
+    }
+
+    #[test]
+    fn adder_test() {
+        let parsed = parse(&adder(6, 45, 29, &[])).unwrap();
+        assert_eq!(74, part1(&parsed).unwrap());
+        assert_eq!("", part2(&parsed));
+    }
+
+    #[test]
+    fn part2_test() {
+        let swaps = [("s01", "a01"), ("z02", "t02"), ("z04", "c04")];
+        let parsed = parse(&adder(6, 45, 29, &swaps)).unwrap();
+
+        let expected = "a01,c04,s01,t02,z02,z04";
+        let actual = part2(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+}
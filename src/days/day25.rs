@@ -0,0 +1,113 @@
+use aoc_lib::{Bench, BenchResult, Day, NoError, ParseResult, UserError};
+use color_eyre::{Report, Result, eyre::eyre};
+
+pub const DAY: Day = Day {
+    day: 25,
+    name: "Code Chronicle",
+    part_1: run_part1,
+    part_2: None,
+    other: &[("Parse", run_parse)],
+};
+
+fn run_part1(input: &str, b: Bench) -> BenchResult {
+    let data = parse(input).map_err(UserError)?;
+    b.bench(|| Ok::<_, NoError>(part1(&data)))
+}
+
+fn run_parse(input: &str, b: Bench) -> BenchResult {
+    b.bench(|| {
+        let data = parse(input).map_err(UserError)?;
+        Ok::<_, Report>(ParseResult(data))
+    })
+}
+
+/// Column heights packed into one nibble each, with column 0 in the lowest nibble.
+type Heights = u32;
+
+#[derive(Debug)]
+struct Schematics {
+    locks: Vec<Heights>,
+    keys: Vec<Heights>,
+}
+
+fn parse(input: &str) -> Result<Schematics> {
+    let mut schematics = Schematics {
+        locks: Vec::new(),
+        keys: Vec::new(),
+    };
+
+    for block in input.trim().split("\n\n") {
+        let rows: Vec<_> = block.lines().map(|l| l.trim().as_bytes()).collect();
+        if rows.len() != 7 || rows.iter().any(|r| r.len() != 5) {
+            return Err(eyre!("Schematic is not 7x5:\n{block}"));
+        }
+
+        let mut heights = 0;
+        for col in 0..5 {
+            // Don't count the full top or bottom row.
+            let height = rows[1..6].iter().filter(|r| r[col] == b'#').count() as u32;
+            heights |= height << (col * 4);
+        }
+
+        if rows[0] == b"#####" {
+            schematics.locks.push(heights);
+        } else if rows[6] == b"#####" {
+            schematics.keys.push(heights);
+        } else {
+            return Err(eyre!("Schematic is neither a lock nor a key:\n{block}"));
+        }
+    }
+
+    Ok(schematics)
+}
+
+fn part1(schematics: &Schematics) -> usize {
+    // A column fits if the heights add up to at most 5. Adding 2 to each column moves that limit
+    // to 7, so any overlapping column sets the top bit of its nibble.
+    const BIAS: Heights = 0x22222;
+    const OVERLAP: Heights = 0x88888;
+
+    schematics
+        .locks
+        .iter()
+        .map(|&lock| {
+            schematics
+                .keys
+                .iter()
+                .filter(|&&key| (lock + key + BIAS) & OVERLAP == 0)
+                .count()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_lib::Example;
+
+    #[test]
+    fn parse_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        assert_eq!(parsed.locks, [0x34350, 0x35021]);
+        assert_eq!(parsed.keys, [0x31205, 0x20434, 0x10203]);
+    }
+
+    #[test]
+    fn part1_test() {
+        let data = aoc_lib::input(DAY.day)
+            .example(Example::Part1, 1)
+            .open()
+            .unwrap();
+
+        let parsed = parse(&data).unwrap();
+        let expected = 3;
+        let actual = part1(&parsed);
+
+        assert_eq!(expected, actual);
+    }
+}
@@ -10,6 +10,12 @@ static ALLOC: TracingAlloc = TracingAlloc;
 
 fn main() -> Result<()> {
     color_eyre::install()?;
+
+    // The day 17 debugger is interactive, so it can't run as one of the day's benches.
+    if let Some(path) = std::env::var_os("DAY17_DEBUG") {
+        return days::run_day17_debugger(path.as_ref());
+    }
+
     aoc_lib::run(&ALLOC, 2024, days::DAYS)?;
 
     Ok(())